@@ -0,0 +1,61 @@
+defmodule ExIceberg.Async do
+  @moduledoc false
+
+  # NIFs that do I/O return `{:ok, handle}` immediately and run on the tokio
+  # runtime, replying with `{ref, result}` to the calling process once done.
+  # This keeps BEAM schedulers free while a catalog request is in flight.
+  #
+  # The handle aborts the task when it is cancelled, garbage collected, or
+  # when the calling process exits, so abandoned work stops consuming I/O.
+
+  alias ExIceberg.{Error, Nif}
+
+  @doc false
+  def call(fun, opts \\ []) when is_function(fun, 1) do
+    timeout = Keyword.get(opts, :timeout, :infinity)
+    ref = make_ref()
+
+    case fun.(ref) do
+      {:ok, handle} -> await(ref, handle, timeout)
+      {:error, %Error{}} = error -> error
+    end
+  end
+
+  # Identity override passed to NIFs that reach the catalog, from the
+  # `:token`, `:credential` or `:subject_token` option. `nil` uses the
+  # catalog's own.
+  @identity_options [:token, :credential, :subject_token]
+
+  @doc false
+  def identity(opts) do
+    case Keyword.take(opts, @identity_options) do
+      [] ->
+        nil
+
+      [{key, value}] when is_binary(value) ->
+        {key, value}
+
+      _ ->
+        raise ArgumentError,
+              "expected one binary :token, :credential or :subject_token option"
+    end
+  end
+
+  # `handle` must stay referenced until the reply arrives, otherwise it could
+  # be garbage collected and cancel the task.
+  defp await(ref, handle, timeout) do
+    receive do
+      {^ref, result} -> result
+    after
+      timeout ->
+        :ok = Nif.task_cancel(handle)
+
+        # The reply may have been sent right before the task was cancelled
+        receive do
+          {^ref, result} -> result
+        after
+          0 -> {:error, %Error{kind: :timeout, message: "Operation timed out after #{timeout}ms"}}
+        end
+    end
+  end
+end
@@ -0,0 +1,314 @@
+defmodule ExIceberg.Catalog do
+  @moduledoc """
+  Catalog operations shared by every catalog type.
+
+  `load/2` builds a catalog from its properties, like pyiceberg's
+  `load_catalog`, with the catalog type given by the `"type"` property:
+
+      catalog = ExIceberg.Catalog.load("local", %{"type" => "memory"})
+
+      catalog =
+        ExIceberg.Catalog.load("prod", %{
+          "type" => "rest",
+          "uri" => "http://localhost:8181",
+          "credential" => "client_id:client_secret"
+        })
+
+  Supported types and their main properties:
+
+    * `"rest"` - `"uri"`, `"warehouse"`, `"token"`, `"credential"`,
+      `"oauth2-server-uri"`, `"scope"`, `"audience"`, `"resource"` and
+      `"header.*"`, see `ExIceberg.Rest.Catalog`
+    * `"memory"` - `"warehouse"`, see `ExIceberg.Memory.Catalog`
+    * `"sql"` - `"uri"` and `"warehouse"`, see `ExIceberg.Sql.Catalog`
+    * `"glue"` - `"warehouse"`, `"glue.region"`, `"glue.endpoint"`,
+      `"glue.id"`, `"glue.access-key-id"`, `"glue.secret-access-key"`,
+      `"glue.session-token"` and `"glue.profile-name"`, see
+      `ExIceberg.Glue.Catalog`
+    * `"hive"` - `"uri"` (the metastore's thrift address), `"warehouse"` and
+      `"thrift_transport"`, see `ExIceberg.Hms.Catalog`
+
+  Other properties are passed to the catalog as is, including FileIO
+  properties such as `"s3.endpoint"`, see `ExIceberg.FileIO`.
+
+  The functions below work with catalogs of any type, including the ones
+  built with `ExIceberg.Rest.Catalog.new/2`, `ExIceberg.Memory.Catalog.new/2`,
+  `ExIceberg.Sql.Catalog.new/2`, `ExIceberg.Glue.Catalog.new/2` and
+  `ExIceberg.Hms.Catalog.new/2`. They return `{:ok, catalog, value}` or
+  `{:error, catalog, %ExIceberg.Error{}}` and accept the options described
+  in `ExIceberg.Rest.Catalog`. `:token`, `:credential` and `:subject_token`
+  are only supported by REST catalogs.
+  """
+
+  alias ExIceberg.{Async, Nif}
+  alias ExIceberg.{Error, NamespaceIdent, TableIdent}
+
+  defstruct name: nil, type: nil, properties: %{}, nif_catalog_resource: nil
+
+  @type t :: %__MODULE__{
+          name: String.t(),
+          type: String.t(),
+          properties: %{optional(String.t()) => String.t()},
+          nif_catalog_resource: reference()
+        }
+
+  @doc """
+  Builds a catalog from its properties.
+
+  The `"type"` property selects the catalog, see the module docs. Keys and
+  values are converted to strings. SQL catalogs store their tables under
+  `name`.
+
+  Raises `ExIceberg.Error` for an unsupported type or invalid properties.
+
+  ## Examples
+
+      catalog =
+        ExIceberg.Catalog.load("local", %{
+          type: "sql",
+          uri: "sqlite:///tmp/catalog.db?mode=rwc",
+          warehouse: "file:///tmp/warehouse"
+        })
+  """
+  def load(name, properties) do
+    {type, properties} = properties |> ExIceberg.FileIO.properties() |> Map.pop("type")
+
+    unless type do
+      raise ArgumentError, "expected a \"type\" catalog property"
+    end
+
+    nif_properties = if type == "sql", do: Map.put(properties, "name", name), else: properties
+
+    nif_catalog_resource =
+      case Async.call(&Nif.catalog_load(type, nif_properties, &1)) do
+        {:ok, nif_catalog_resource} -> nif_catalog_resource
+        {:error, %Error{} = error} -> raise error
+      end
+
+    %__MODULE__{
+      name: name,
+      type: type,
+      properties: properties,
+      nif_catalog_resource: nif_catalog_resource
+    }
+  end
+
+  @doc """
+  Lists all namespaces in the catalog.
+  """
+  def list_namespaces(%{nif_catalog_resource: nif_catalog_resource} = catalog, opts \\ []) do
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.catalog_list_namespaces(nif_catalog_resource, identity, &1), opts) do
+      {:ok, namespaces} -> {:ok, catalog, namespaces}
+      {:error, %Error{} = error} -> {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Creates a new namespace in the catalog.
+  """
+  def create_namespace(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %NamespaceIdent{} = namespace,
+        properties \\ %{},
+        opts \\ []
+      ) do
+    identity = Async.identity(opts)
+
+    case Async.call(
+           &Nif.catalog_create_namespace(
+             nif_catalog_resource,
+             namespace,
+             properties,
+             identity,
+             &1
+           ),
+           opts
+         ) do
+      {:ok, response} -> {:ok, catalog, response}
+      {:error, %Error{} = error} -> {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Drops a namespace from the catalog. It must be empty unless
+  `cascade: true` is given, see `ExIceberg.Rest.Catalog.drop_namespace/3`.
+  """
+  def drop_namespace(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %NamespaceIdent{} = namespace,
+        opts \\ []
+      ) do
+    identity = Async.identity(opts)
+
+    nif_fun =
+      if Keyword.get(opts, :cascade, false) do
+        purge = Keyword.get(opts, :purge, false)
+        dry_run = Keyword.get(opts, :dry_run, false)
+        &Nif.catalog_drop_namespace_cascade(&1, &2, purge, dry_run, &3, &4)
+      else
+        &Nif.catalog_drop_namespace/4
+      end
+
+    case Async.call(&nif_fun.(nif_catalog_resource, namespace, identity, &1), opts) do
+      {:ok, response} -> {:ok, catalog, response}
+      {:error, %Error{} = error} -> {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Checks if a table exists in the catalog.
+  """
+  def table_exists?(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %TableIdent{} = table_ident,
+        opts \\ []
+      ) do
+    identity = Async.identity(opts)
+
+    case Async.call(
+           &Nif.catalog_table_exists(nif_catalog_resource, table_ident, identity, &1),
+           opts
+         ) do
+      {:ok, exists} -> {:ok, catalog, exists}
+      {:error, %Error{} = error} -> {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Drops a table from the catalog, deleting its files too with `purge: true`,
+  see `ExIceberg.Rest.Catalog.drop_table/3`.
+  """
+  def drop_table(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %TableIdent{} = table_ident,
+        opts \\ []
+      ) do
+    identity = Async.identity(opts)
+
+    nif_fun =
+      if Keyword.get(opts, :purge, false),
+        do: &Nif.catalog_purge_table/4,
+        else: &Nif.catalog_drop_table/4
+
+    case Async.call(&nif_fun.(nif_catalog_resource, table_ident, identity, &1), opts) do
+      {:ok, response} -> {:ok, catalog, response}
+      {:error, %Error{} = error} -> {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Creates a table in the catalog from a list of `ExIceberg.Types.Field`
+  structs, see also `ExIceberg.Schema`.
+  """
+  def create_table(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %TableIdent{} = table_ident,
+        fields,
+        properties \\ %{},
+        opts \\ []
+      ) do
+    identity = Async.identity(opts)
+
+    case Async.call(
+           &Nif.catalog_create_table(
+             nif_catalog_resource,
+             table_ident,
+             fields,
+             properties,
+             identity,
+             &1
+           ),
+           opts
+         ) do
+      {:ok, table_resource} ->
+        table = ExIceberg.Table.new(table_resource)
+        {:ok, catalog, table}
+
+      {:error, %Error{} = error} ->
+        {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Loads a table from the catalog.
+  """
+  def load_table(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %TableIdent{} = table_ident,
+        opts \\ []
+      ) do
+    identity = Async.identity(opts)
+
+    case Async.call(
+           &Nif.catalog_load_table(nif_catalog_resource, table_ident, identity, &1),
+           opts
+         ) do
+      {:ok, table_resource} ->
+        table = ExIceberg.Table.new(table_resource)
+        {:ok, catalog, table}
+
+      {:error, %Error{} = error} ->
+        {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Registers an existing table in the catalog from the location of its
+  metadata file, e.g. to move tables between catalogs.
+  """
+  def register_table(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %TableIdent{} = table_ident,
+        metadata_location,
+        opts \\ []
+      )
+      when is_binary(metadata_location) do
+    identity = Async.identity(opts)
+
+    case Async.call(
+           &Nif.catalog_register_table(
+             nif_catalog_resource,
+             table_ident,
+             metadata_location,
+             identity,
+             &1
+           ),
+           opts
+         ) do
+      {:ok, table_resource} ->
+        table = ExIceberg.Table.new(table_resource)
+        {:ok, catalog, table}
+
+      {:error, %Error{} = error} ->
+        {:error, catalog, error}
+    end
+  end
+
+  @doc """
+  Renames a table in the catalog.
+  """
+  def rename_table(
+        %{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %TableIdent{} = src_table_ident,
+        %TableIdent{} = dest_table_ident,
+        opts \\ []
+      ) do
+    identity = Async.identity(opts)
+
+    case Async.call(
+           &Nif.catalog_rename_table(
+             nif_catalog_resource,
+             src_table_ident,
+             dest_table_ident,
+             identity,
+             &1
+           ),
+           opts
+         ) do
+      {:ok, response} -> {:ok, catalog, response}
+      {:error, %Error{} = error} -> {:error, catalog, error}
+    end
+  end
+end
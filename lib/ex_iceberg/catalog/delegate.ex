@@ -0,0 +1,143 @@
+defmodule ExIceberg.Catalog.Delegate do
+  @moduledoc false
+
+  # Defines the namespace and table operations of a catalog module as
+  # delegates to `ExIceberg.Catalog`, which runs them against the catalog's
+  # NIF resource whatever its type. The module only defines its struct and
+  # `new/2`.
+  #
+  # Options:
+  #
+  #   * `:register_table` - note appended to the `register_table/4` docs,
+  #     e.g. when the catalog does not support it
+
+  alias ExIceberg.{NamespaceIdent, TableIdent}
+
+  defmacro __using__(opts) do
+    register_table_doc = """
+    Registers an existing table in the catalog from the location of its
+    metadata file.
+
+    Returns `{:ok, catalog, table}` or `{:error, catalog, error}`, see
+    `ExIceberg.Rest.Catalog.register_table/4`.
+    """
+
+    register_table_doc =
+      case Keyword.get(opts, :register_table) do
+        nil -> register_table_doc
+        note -> register_table_doc <> "\n" <> note <> "\n"
+      end
+
+    quote do
+      @doc """
+      Lists all namespaces in the catalog.
+
+      Returns `{:ok, catalog, namespaces}` or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.list_namespaces/2`.
+      """
+      def list_namespaces(%__MODULE__{} = catalog, opts \\ []) do
+        ExIceberg.Catalog.list_namespaces(catalog, opts)
+      end
+
+      @doc """
+      Creates a new namespace in the catalog.
+
+      Returns `{:ok, catalog, namespace_ident}` or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.create_namespace/4`.
+      """
+      def create_namespace(
+            %__MODULE__{} = catalog,
+            %NamespaceIdent{} = namespace,
+            properties \\ %{},
+            opts \\ []
+          ) do
+        ExIceberg.Catalog.create_namespace(catalog, namespace, properties, opts)
+      end
+
+      @doc """
+      Drops a namespace from the catalog, with its tables and nested namespaces
+      when `cascade: true` is given. This catalog has no views.
+
+      Returns `{:ok, catalog, namespace_ident}`, `{:ok, catalog, results}` with
+      `cascade: true`, or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.drop_namespace/3`.
+      """
+      def drop_namespace(%__MODULE__{} = catalog, %NamespaceIdent{} = namespace, opts \\ []) do
+        ExIceberg.Catalog.drop_namespace(catalog, namespace, opts)
+      end
+
+      @doc """
+      Checks if a table exists in the catalog.
+
+      Returns `{:ok, catalog, exists}` or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.table_exists?/3`.
+      """
+      def table_exists?(%__MODULE__{} = catalog, %TableIdent{} = table_ident, opts \\ []) do
+        ExIceberg.Catalog.table_exists?(catalog, table_ident, opts)
+      end
+
+      @doc """
+      Drops a table from the catalog, its files are left in the warehouse unless
+      `purge: true` is given.
+
+      Returns `{:ok, catalog, table_ident}` or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.drop_table/3`.
+      """
+      def drop_table(%__MODULE__{} = catalog, %TableIdent{} = table_ident, opts \\ []) do
+        ExIceberg.Catalog.drop_table(catalog, table_ident, opts)
+      end
+
+      @doc """
+      Creates a table in the catalog from a list of `ExIceberg.Types.Field`
+      structs, or through `ExIceberg.Schema`.
+
+      Returns `{:ok, catalog, table}` or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.create_table/5`.
+      """
+      def create_table(
+            %__MODULE__{} = catalog,
+            %TableIdent{} = table_ident,
+            fields,
+            properties \\ %{},
+            opts \\ []
+          ) do
+        ExIceberg.Catalog.create_table(catalog, table_ident, fields, properties, opts)
+      end
+
+      @doc """
+      Loads a table from the catalog.
+
+      Returns `{:ok, catalog, table}` or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.load_table/3`.
+      """
+      def load_table(%__MODULE__{} = catalog, %TableIdent{} = table_ident, opts \\ []) do
+        ExIceberg.Catalog.load_table(catalog, table_ident, opts)
+      end
+
+      @doc unquote(register_table_doc)
+      def register_table(
+            %__MODULE__{} = catalog,
+            %TableIdent{} = table_ident,
+            metadata_location,
+            opts \\ []
+          ) do
+        ExIceberg.Catalog.register_table(catalog, table_ident, metadata_location, opts)
+      end
+
+      @doc """
+      Renames a table in the catalog.
+
+      Returns `{:ok, catalog, response}` or `{:error, catalog, error}`, see
+      `ExIceberg.Rest.Catalog.rename_table/4`.
+      """
+      def rename_table(
+            %__MODULE__{} = catalog,
+            %TableIdent{} = src_table_ident,
+            %TableIdent{} = dest_table_ident,
+            opts \\ []
+          ) do
+        ExIceberg.Catalog.rename_table(catalog, src_table_ident, dest_table_ident, opts)
+      end
+    end
+  end
+end
@@ -0,0 +1,62 @@
+defmodule ExIceberg.Error do
+  @moduledoc """
+  Error returned by catalog and table operations.
+
+  The `kind` field classifies the failure so callers can pattern match on it
+  instead of parsing the message:
+
+  - `:no_such_table` - The table does not exist
+  - `:no_such_namespace` - The namespace does not exist
+  - `:already_exists` - The table or namespace already exists
+  - `:namespace_not_empty` - The namespace still contains tables or namespaces
+  - `:commit_failed` - A commit conflicted with a concurrent change
+  - `:commit_state_unknown` - The server failed while committing, the outcome is unknown
+  - `:unauthorized` - Authentication with the catalog failed
+  - `:forbidden` - The caller is not allowed to perform the operation
+  - `:not_found` - The catalog returned 404 for an unknown resource
+  - `:invalid_argument` - The request was rejected as malformed
+  - `:not_supported` - The operation is not supported by the catalog
+  - `:precondition_failed` - The operation requirements were not met
+  - `:service_unavailable` - The catalog is temporarily unavailable
+  - `:timeout` - The operation did not complete within the `:timeout` option
+  - `:data_invalid` - Metadata or a response could not be parsed
+  - `:unexpected` - Any other failure, such as a connection error
+
+  `http_status` is set when the failure came from an HTTP response and
+  `source` carries the underlying cause, when there is one.
+
+  ## Examples
+
+      case ExIceberg.Rest.Catalog.load_table(catalog, table_ident) do
+        {:ok, catalog, table} -> {:ok, catalog, table}
+        {:error, catalog, %ExIceberg.Error{kind: :no_such_table}} -> create(catalog)
+      end
+  """
+
+  defexception [:kind, :message, :http_status, :source]
+
+  @type kind ::
+          :no_such_table
+          | :no_such_namespace
+          | :already_exists
+          | :namespace_not_empty
+          | :commit_failed
+          | :commit_state_unknown
+          | :unauthorized
+          | :forbidden
+          | :not_found
+          | :invalid_argument
+          | :not_supported
+          | :precondition_failed
+          | :service_unavailable
+          | :timeout
+          | :data_invalid
+          | :unexpected
+
+  @type t :: %__MODULE__{
+          kind: kind(),
+          message: String.t(),
+          http_status: non_neg_integer() | nil,
+          source: String.t() | nil
+        }
+end
@@ -0,0 +1,40 @@
+defmodule ExIceberg.FileIO do
+  @moduledoc """
+  Settings for the storage a catalog's tables read and write their files with.
+
+  Catalogs take FileIO properties under their `:file_io` option. Tables loaded
+  from the catalog use them for their data and metadata files:
+
+      file_io: %{
+        "s3.endpoint" => "http://localhost:9000",
+        "s3.region" => "local-01",
+        "s3.access-key-id" => "minio-root-user",
+        "s3.secret-access-key" => "minio-root-password",
+        "s3.path-style-access" => true
+      }
+
+  Supported storage, chosen from the table or warehouse location:
+
+    * `s3://` - S3 and S3-compatible storage such as MinIO. Common properties
+      are `"s3.endpoint"`, `"s3.region"`, `"s3.access-key-id"`,
+      `"s3.secret-access-key"`, `"s3.session-token"` and `"s3.path-style-access"`
+    * `file://` - the local filesystem, e.g. a temporary directory in tests
+    * `memory://` - in-memory storage, only shared by a table and its copies
+
+  Values may be strings, booleans or numbers. Properties given here take
+  precedence over the ones returned by the catalog, including vended
+  credentials.
+
+  Memory and SQL catalogs write their tables' files themselves, so their
+  warehouse location, e.g. `"file:///tmp/warehouse"`, is checked when the
+  catalog is created, raising `ExIceberg.Error` with kind `:invalid_argument`
+  for unsupported storage or invalid properties. REST catalogs accept any
+  warehouse, unsupported storage is only reported once a table's files are
+  read or written.
+  """
+
+  @doc false
+  def properties(properties) do
+    Map.new(properties, fn {key, value} -> {to_string(key), to_string(value)} end)
+  end
+end
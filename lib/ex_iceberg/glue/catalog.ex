@@ -0,0 +1,96 @@
+defmodule ExIceberg.Glue.Catalog do
+  @moduledoc """
+  AWS Glue catalog implementation using Rust NIFs.
+
+  Namespaces are Glue databases and tables are Glue tables pointing at their
+  Iceberg metadata, with table files in an S3 warehouse:
+
+      catalog =
+        ExIceberg.Glue.Catalog.new("prod", %{
+          warehouse: "s3://my-bucket/warehouse",
+          region: "eu-west-1"
+        })
+
+  Region and credentials not given in the config are resolved like the AWS
+  SDKs do, from the environment, the shared config files or the instance
+  metadata. They are also used by the S3 FileIO unless set in `:file_io`.
+
+  ## Options
+
+  Functions that talk to the catalog accept a keyword list of options:
+
+    * `:timeout` - time in milliseconds to wait for the catalog, defaults to
+      `:infinity`. On timeout `%ExIceberg.Error{kind: :timeout}` is returned.
+
+  Tables loaded from this catalog do not support vended credentials or
+  remote signing.
+  """
+
+  alias ExIceberg.{Async, Error, Nif}
+  alias ExIceberg.Glue.CatalogConfig
+
+  defstruct name: nil, config: nil, nif_catalog_resource: nil
+
+  @type t :: %__MODULE__{
+          name: String.t(),
+          config: CatalogConfig.t(),
+          nif_catalog_resource: reference()
+        }
+
+  use ExIceberg.Catalog.Delegate
+
+  @doc """
+  Creates a new Glue catalog instance.
+
+  ## Parameters
+
+  - `name` - The name of the catalog
+  - `config` - Configuration map:
+
+    * `:warehouse` - location of the tables, e.g. `"s3://my-bucket/warehouse"`
+    * `:region` - AWS region of the catalog
+    * `:endpoint` - Glue endpoint override, e.g. `"http://localhost:5000"`
+      for a local moto server
+    * `:catalog_id` - AWS account id of the catalog, defaults to the caller's
+    * `:access_key_id`, `:secret_access_key` and `:session_token` - static
+      AWS credentials
+    * `:profile` - profile of the shared AWS config files
+    * `:properties` - map of other catalog properties
+    * `:file_io` - map of FileIO properties, see `ExIceberg.FileIO`
+
+  The catalog is not contacted until it is used. Raises `ExIceberg.Error` if
+  the config is invalid.
+
+  ## Examples
+
+      catalog =
+        ExIceberg.Glue.Catalog.new("local", %{
+          warehouse: "s3://warehouse/glue",
+          endpoint: "http://localhost:5000",
+          region: "us-east-1",
+          access_key_id: "testing",
+          secret_access_key: "testing"
+        })
+  """
+  def new(name, config) do
+    config = struct(CatalogConfig, config)
+
+    config = %{
+      config
+      | properties: ExIceberg.FileIO.properties(config.properties),
+        file_io: ExIceberg.FileIO.properties(config.file_io)
+    }
+
+    nif_catalog_resource =
+      case Async.call(&Nif.glue_catalog_new(config, &1)) do
+        {:ok, nif_catalog_resource} -> nif_catalog_resource
+        {:error, %Error{} = error} -> raise error
+      end
+
+    %__MODULE__{
+      name: name,
+      config: config,
+      nif_catalog_resource: nif_catalog_resource
+    }
+  end
+end
@@ -0,0 +1,29 @@
+defmodule ExIceberg.Glue.CatalogConfig do
+  @moduledoc false
+
+  defstruct [
+    :warehouse,
+    :region,
+    :endpoint,
+    :catalog_id,
+    :access_key_id,
+    :secret_access_key,
+    :session_token,
+    :profile,
+    properties: %{},
+    file_io: %{}
+  ]
+
+  @type t :: %__MODULE__{
+          warehouse: String.t(),
+          region: String.t() | nil,
+          endpoint: String.t() | nil,
+          catalog_id: String.t() | nil,
+          access_key_id: String.t() | nil,
+          secret_access_key: String.t() | nil,
+          session_token: String.t() | nil,
+          profile: String.t() | nil,
+          properties: %{optional(String.t()) => String.t()},
+          file_io: %{optional(String.t()) => String.t()}
+        }
+end
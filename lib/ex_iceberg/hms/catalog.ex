@@ -0,0 +1,90 @@
+defmodule ExIceberg.Hms.Catalog do
+  @moduledoc """
+  Hive Metastore catalog implementation using Rust NIFs.
+
+  Namespaces are Hive databases and tables are external Hive tables pointing
+  at their Iceberg metadata, so tables already registered in a metastore can
+  be used alongside the other catalog types:
+
+      catalog =
+        ExIceberg.Hms.Catalog.new("hive", %{
+          address: "metastore.internal:9083",
+          warehouse: "s3://my-bucket/warehouse"
+        })
+
+  Hive does not support nested namespaces.
+
+  ## Options
+
+  Functions that talk to the catalog accept a keyword list of options:
+
+    * `:timeout` - time in milliseconds to wait for the catalog, defaults to
+      `:infinity`. On timeout `%ExIceberg.Error{kind: :timeout}` is returned.
+
+  Tables loaded from this catalog do not support vended credentials or
+  remote signing.
+  """
+
+  alias ExIceberg.{Async, Error, Nif}
+  alias ExIceberg.Hms.CatalogConfig
+
+  defstruct name: nil, config: nil, nif_catalog_resource: nil
+
+  @type t :: %__MODULE__{
+          name: String.t(),
+          config: CatalogConfig.t(),
+          nif_catalog_resource: reference()
+        }
+
+  use ExIceberg.Catalog.Delegate,
+    register_table:
+      "Not supported by the Hive Metastore catalog yet, returns an error with kind `:not_supported`."
+
+  @doc """
+  Creates a new Hive Metastore catalog instance.
+
+  ## Parameters
+
+  - `name` - The name of the catalog
+  - `config` - Configuration map:
+
+    * `:address` - thrift address of the metastore, e.g. `"localhost:9083"`
+    * `:warehouse` - location of the tables, e.g. `"s3://my-bucket/warehouse"`
+    * `:thrift_transport` - `"buffered"` (the default) or `"framed"`
+    * `:properties` - map of other catalog properties
+    * `:file_io` - map of FileIO properties, see `ExIceberg.FileIO`
+
+  The address is resolved here, but the metastore is only contacted once the
+  catalog is used. Raises `ExIceberg.Error` if the address cannot be resolved
+  or the config is invalid.
+
+  ## Examples
+
+      catalog =
+        ExIceberg.Hms.Catalog.new("hive", %{
+          address: "localhost:9083",
+          warehouse: "file:///tmp/warehouse"
+        })
+  """
+  def new(name, config) do
+    config = struct(CatalogConfig, config)
+
+    config = %{
+      config
+      | properties: ExIceberg.FileIO.properties(config.properties),
+        file_io: ExIceberg.FileIO.properties(config.file_io)
+    }
+
+    nif_catalog_resource =
+      case Async.call(&Nif.hms_catalog_new(config, &1)) do
+        {:ok, nif_catalog_resource} -> nif_catalog_resource
+        {:error, %Error{} = error} -> raise error
+      end
+
+    %__MODULE__{
+      name: name,
+      config: config,
+      nif_catalog_resource: nif_catalog_resource
+    }
+  end
+end
@@ -0,0 +1,19 @@
+defmodule ExIceberg.Hms.CatalogConfig do
+  @moduledoc false
+
+  defstruct [
+    :address,
+    :warehouse,
+    :thrift_transport,
+    properties: %{},
+    file_io: %{}
+  ]
+
+  @type t :: %__MODULE__{
+          address: String.t(),
+          warehouse: String.t(),
+          thrift_transport: String.t() | nil,
+          properties: %{optional(String.t()) => String.t()},
+          file_io: %{optional(String.t()) => String.t()}
+        }
+end
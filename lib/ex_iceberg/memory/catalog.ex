@@ -0,0 +1,73 @@
+defmodule ExIceberg.Memory.Catalog do
+  @moduledoc """
+  In-memory catalog implementation using Rust NIFs.
+
+  Namespaces and tables are kept in the catalog itself and are gone once it
+  is garbage collected, which makes it handy for tests and local pipelines
+  that do not need a catalog server. Table metadata and data files are
+  written to the warehouse, in memory by default or e.g. to a local
+  directory:
+
+      catalog = ExIceberg.Memory.Catalog.new("local", %{warehouse: "file:///tmp/warehouse"})
+
+  All tables of the catalog share its FileIO, so they can read each other's
+  files from a `memory://` warehouse too.
+
+  ## Options
+
+  Functions that talk to the catalog accept a keyword list of options:
+
+    * `:timeout` - time in milliseconds to wait for the catalog, defaults to
+      `:infinity`. On timeout `%ExIceberg.Error{kind: :timeout}` is returned.
+
+  Tables loaded from this catalog do not support vended credentials or
+  remote signing.
+  """
+
+  alias ExIceberg.{Async, Error, Nif}
+  alias ExIceberg.Memory.CatalogConfig
+
+  defstruct name: nil, config: nil, nif_catalog_resource: nil
+
+  @type t :: %__MODULE__{
+          name: String.t(),
+          config: CatalogConfig.t(),
+          nif_catalog_resource: reference()
+        }
+
+  use ExIceberg.Catalog.Delegate
+
+  @doc """
+  Creates a new in-memory catalog instance.
+
+  ## Parameters
+
+  - `name` - The name of the catalog
+  - `config` - Configuration map, all keys are optional:
+
+    * `:warehouse` - location of the tables, defaults to `"memory://warehouse"`
+    * `:file_io` - map of FileIO properties, see `ExIceberg.FileIO`
+
+  Raises `ExIceberg.Error` for an unsupported warehouse location.
+
+  ## Examples
+
+      catalog = ExIceberg.Memory.Catalog.new("test")
+  """
+  def new(name, config \\ %{}) do
+    config = struct(CatalogConfig, config)
+    config = %{config | file_io: ExIceberg.FileIO.properties(config.file_io)}
+
+    nif_catalog_resource =
+      case Async.call(&Nif.memory_catalog_new(config, &1)) do
+        {:ok, nif_catalog_resource} -> nif_catalog_resource
+        {:error, %Error{} = error} -> raise error
+      end
+
+    %__MODULE__{
+      name: name,
+      config: config,
+      nif_catalog_resource: nif_catalog_resource
+    }
+  end
+end
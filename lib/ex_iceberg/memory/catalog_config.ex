@@ -0,0 +1,11 @@
+defmodule ExIceberg.Memory.CatalogConfig do
+  @moduledoc false
+
+  defstruct warehouse: "memory://warehouse",
+            file_io: %{}
+
+  @type t :: %__MODULE__{
+          warehouse: String.t(),
+          file_io: %{optional(String.t()) => String.t()}
+        }
+end
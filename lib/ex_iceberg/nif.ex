@@ -20,33 +20,130 @@ defmodule ExIceberg.Nif do
       arm-unknown-linux-gnueabihf
     ),
     nif_versions: ["2.15"],
+    load_data_fun: {ExIceberg.Runtime, :load_data},
     force_build: System.get_env("EX_ICEBERG_BUILD") in ["1", "true"]
 
   # REST Catalog NIF functions
-  def rest_catalog_new(_config), do: :erlang.nif_error(:nif_not_loaded)
-  def rest_catalog_list_namespaces(_catalog_resource), do: :erlang.nif_error(:nif_not_loaded)
+  #
+  # Functions taking a `ref` run asynchronously, return `{:ok, handle}` and
+  # reply with `{ref, result}`, see `ExIceberg.Async`. An `identity` of
+  # `{:token, token}`, `{:credential, credential}` or
+  # `{:subject_token, token}` overrides the catalog's own for that call,
+  # `nil` keeps it.
+  def rest_catalog_new(_config, _ref), do: :erlang.nif_error(:nif_not_loaded)
 
-  def rest_catalog_create_namespace(_catalog_resource, _namespace_ident, _properties),
+  def rest_catalog_token_info(_catalog_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  def rest_catalog_refresh_token(_catalog_resource, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def rest_catalog_config(_catalog_resource, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  # Memory, SQL, Glue and HMS Catalog NIF functions, returning a catalog resource
+  # used with the catalog functions below
+  def memory_catalog_new(_config, _ref), do: :erlang.nif_error(:nif_not_loaded)
+  def sql_catalog_new(_config, _ref), do: :erlang.nif_error(:nif_not_loaded)
+  def glue_catalog_new(_config, _ref), do: :erlang.nif_error(:nif_not_loaded)
+  def hms_catalog_new(_config, _ref), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Catalog NIF functions working with every catalog type, an identity is
+  # only supported by REST catalogs
+  def catalog_load(_type, _properties, _ref), do: :erlang.nif_error(:nif_not_loaded)
+
+  def catalog_list_namespaces(_catalog_resource, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def catalog_create_namespace(
+        _catalog_resource,
+        _namespace_ident,
+        _properties,
+        _identity,
+        _ref
+      ),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def catalog_drop_namespace(_catalog_resource, _namespace_ident, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def catalog_drop_namespace_cascade(
+        _catalog_resource,
+        _namespace_ident,
+        _purge,
+        _dry_run,
+        _identity,
+        _ref
+      ),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def catalog_table_exists(_catalog_resource, _table_ident, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def catalog_drop_table(_catalog_resource, _table_ident, _identity, _ref),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def rest_catalog_table_exists(_catalog_resource, _table_ident),
+  def catalog_purge_table(_catalog_resource, _table_ident, _identity, _ref),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def rest_catalog_drop_table(_catalog_resource, _table_ident),
+  def catalog_create_table(
+        _catalog_resource,
+        _table_ident,
+        _fields,
+        _properties,
+        _identity,
+        _ref
+      ),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def rest_catalog_create_table(_catalog_resource, _table_ident, _fields, _properties),
+  def catalog_load_table(_catalog_resource, _table_ident, _identity, _ref),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def rest_catalog_load_table(_catalog_resource, _table_ident),
+  def catalog_register_table(
+        _catalog_resource,
+        _table_ident,
+        _metadata_location,
+        _identity,
+        _ref
+      ),
     do: :erlang.nif_error(:nif_not_loaded)
 
-  def rest_catalog_rename_table(_catalog_resource, _src_table_ident, _dest_table_ident),
+  def catalog_rename_table(
+        _catalog_resource,
+        _src_table_ident,
+        _dest_table_ident,
+        _identity,
+        _ref
+      ),
     do: :erlang.nif_error(:nif_not_loaded)
 
   # Table operations using SmartTableResource
-  def table_metadata(_table_resource), do: :erlang.nif_error(:nif_not_loaded)
-  def table_metadata_ref(_table_resource), do: :erlang.nif_error(:nif_not_loaded)
-  def table_inspect(_table_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def static_table_from_metadata(_metadata_location, _table_ident, _file_io, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def table_metadata(_table_resource, _identity, _ref), do: :erlang.nif_error(:nif_not_loaded)
+
+  def table_metadata_ref(_table_resource, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def table_inspect(_table_resource, _identity, _ref), do: :erlang.nif_error(:nif_not_loaded)
+
+  def table_scan(_table_resource, _columns, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def scan_next_batch(_scan_resource, _ref), do: :erlang.nif_error(:nif_not_loaded)
+
+  def table_identifier(_table_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def table_refresh(_table_resource, _identity, _ref), do: :erlang.nif_error(:nif_not_loaded)
+  def table_vended_credentials(_table_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  def table_refresh_credentials(_table_resource, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def table_sign_request(_table_resource, _request, _identity, _ref),
+    do: :erlang.nif_error(:nif_not_loaded)
+
   def table_invalidate_cache(_table_resource), do: :erlang.nif_error(:nif_not_loaded)
+
+  # Handle returned by asynchronous NIFs
+  def task_cancel(_handle), do: :erlang.nif_error(:nif_not_loaded)
 end
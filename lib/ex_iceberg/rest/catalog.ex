@@ -1,11 +1,41 @@
 defmodule ExIceberg.Rest.Catalog do
   @moduledoc """
   REST catalog implementation using Rust NIFs.
+
+  ## Options
+
+  Functions that talk to the catalog accept a keyword list of options:
+
+    * `:timeout` - time in milliseconds to wait for the catalog, defaults to
+      `:infinity`. On timeout the request is aborted and
+      `%ExIceberg.Error{kind: :timeout}` is returned.
+
+    * `:token` - bearer token used for this call instead of the catalog's own,
+      e.g. to act on behalf of a tenant in a multi-tenant service.
+
+    * `:credential` - OAuth2 client credential (`"client_id:client_secret"`)
+      exchanged for a token used for this call only. The token endpoint and
+      scope are the ones configured for the catalog.
+
+    * `:subject_token` - token of a user, exchanged (RFC 8693) for a token
+      used for this call only, to act on behalf of that user. The exchange
+      uses the catalog's token endpoint, `subject_token_type`, and its
+      `credential` and `actor_token` when configured.
+
+  A request is also aborted when the calling process exits, e.g. when a
+  `Task` running it is shut down.
+
+  With `:token`, `:credential` or `:subject_token` the call uses a catalog
+  for that identity alone, kept for the 32 most recently used identities. It
+  fetches `/v1/config` once for each token of the identity, and a token
+  requested for a credential or subject token is only requested again when
+  it expires. The catalog's shared state and
+  token are left untouched.
   """
 
-  alias ExIceberg.Nif
+  alias ExIceberg.{Async, Nif}
   alias ExIceberg.Rest.CatalogConfig
-  alias ExIceberg.{NamespaceIdent, TableIdent}
+  alias ExIceberg.{Error, NamespaceIdent, TableIdent}
 
   defstruct name: nil, config: nil, nif_catalog_resource: nil
 
@@ -23,6 +53,58 @@ defmodule ExIceberg.Rest.Catalog do
   - `name` - The name of the catalog
   - `config` - Configuration map containing REST catalog settings
 
+  ## HTTP Options
+
+  The `config` map also accepts settings for the HTTP client, durations are
+  in milliseconds:
+
+    * `:connect_timeout` - timeout for establishing a connection
+    * `:read_timeout` - timeout for each read from the connection
+    * `:proxy` - HTTP(S) proxy URL used for all requests
+    * `:cacertfile` - PEM bundle of CA certificates trusted in addition to the system ones
+    * `:certfile` - PEM client certificate for mTLS, may also contain the key
+    * `:keyfile` - PEM private key for `:certfile`
+    * `:max_retries` - retries of requests rejected with 429 or 5xx, defaults to 3,
+      `0` disables retries
+    * `:retry_base_delay` - delay before the first retry, doubled on each retry, defaults to 100
+    * `:retry_max_delay` - upper bound of the retry delay, defaults to 10000
+
+  Retry delays are randomised between zero and the computed delay. Requests
+  that change the catalog are only retried on 429 and 503.
+
+  ## OAuth2
+
+  With a `:credential` or a `:subject_token`, tokens are requested from
+  `:oauth2_server_uri` (or the catalog's `/v1/oauth/tokens`) and refreshed
+  before they expire:
+
+    * `:credential` - `"client_id:client_secret"` for the client credentials grant
+    * `:scope`, `:audience`, `:resource` - sent with every token request, the scope
+      defaults to `"catalog"`
+    * `:subject_token` - exchange this token for a catalog token (RFC 8693), e.g. to
+      act on behalf of a user
+    * `:subject_token_type` - defaults to `"urn:ietf:params:oauth:token-type:access_token"`
+    * `:actor_token`, `:actor_token_type` - optional actor for delegation
+    * `:token_refresh_margin` - refresh tokens this many milliseconds before they
+      expire, defaults to 60000
+
+  A static `:token` is used as is, or until it expires when a grant is also
+  configured. See `token_info/1` and `refresh_token/2`.
+
+  ## Catalog Properties
+
+    * `:properties` - map of iceberg-rust catalog properties passed through as is,
+      e.g. `"prefix"` or `"s3.region"`
+    * `:headers` - list of `{name, value}` headers sent with every catalog request
+
+  Typed options such as `:uri` or `:credential` take precedence over the same
+  keys in `:properties`.
+
+  ## Storage
+
+    * `:file_io` - map of FileIO properties used by the catalog's tables, e.g.
+      `"s3.endpoint"` or `"s3.path-style-access"`, see `ExIceberg.FileIO`
+
   ## Returns
 
   `%ExIceberg.Rest.Catalog{}` - The catalog struct
@@ -44,14 +126,51 @@ defmodule ExIceberg.Rest.Catalog do
         warehouse: "s3://my-bucket/warehouse"
       }
       catalog = ExIceberg.Rest.Catalog.new("my_catalog", config)
+
+      # Behind a proxy with an internal CA:
+      config = %{
+        uri: "https://catalog.internal",
+        proxy: "http://proxy.internal:3128",
+        cacertfile: "/etc/ssl/internal-ca.pem",
+        connect_timeout: 5_000,
+        max_retries: 3
+      }
+
+      # Acting on behalf of a user with token exchange:
+      config = %{
+        uri: "http://localhost:8181",
+        credential: "client_id:client_secret",
+        oauth2_server_uri: "http://keycloak:8080/realms/iceberg/protocol/openid-connect/token",
+        subject_token: user_access_token,
+        audience: "lakekeeper"
+      }
+
+      # With vended credentials and extra properties:
+      config = %{
+        uri: "http://localhost:8181",
+        headers: [{"X-Iceberg-Access-Delegation", "vended-credentials"}],
+        properties: %{"s3.region" => "eu-west-1"}
+      }
+
+      # With a local MinIO:
+      config = %{
+        uri: "http://localhost:8181",
+        file_io: %{
+          "s3.endpoint" => "http://localhost:9000",
+          "s3.access-key-id" => "minio-root-user",
+          "s3.secret-access-key" => "minio-root-password",
+          "s3.path-style-access" => true
+        }
+      }
   """
   def new(name, config) do
     config = struct(CatalogConfig, config)
+    config = %{config | file_io: ExIceberg.FileIO.properties(config.file_io)}
 
     nif_catalog_resource =
-      case Nif.rest_catalog_new(config) do
+      case Async.call(&Nif.rest_catalog_new(config, &1)) do
         {:ok, nif_catalog_resource} -> nif_catalog_resource
-        {:error, reason} -> raise "Failed to create catalog: #{inspect(reason)}"
+        {:error, %Error{} = error} -> raise error
       end
 
     %__MODULE__{
@@ -67,22 +186,20 @@ defmodule ExIceberg.Rest.Catalog do
   ## Parameters
 
   - `catalog` - The catalog struct
+  - `opts` - Call options, see "Options" in the module docs
 
   ## Returns
 
   `{:ok, updated_catalog, namespaces}` - Success with list of NamespaceIdent structs
-  `{:error, updated_catalog, reason}` - Error with reason
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
 
   ## Examples
 
       {:ok, catalog, namespaces} = ExIceberg.Rest.Catalog.list_namespaces(catalog)
       # namespaces might be [%NamespaceIdent{parts: ["default"]}, %NamespaceIdent{parts: ["analytics"]}]
   """
-  def list_namespaces(%__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog) do
-    case Nif.rest_catalog_list_namespaces(nif_catalog_resource) do
-      {:ok, namespaces} -> {:ok, catalog, namespaces}
-      {:error, [%NamespaceIdent{parts: [reason]}]} -> {:error, catalog, reason}
-    end
+  def list_namespaces(%__MODULE__{} = catalog, opts \\ []) do
+    ExIceberg.Catalog.list_namespaces(catalog, opts)
   end
 
   @doc """
@@ -93,11 +210,12 @@ defmodule ExIceberg.Rest.Catalog do
   - `catalog` - The catalog struct
   - `namespace` - NamespaceIdent struct
   - `properties` - Map of properties for the namespace
+  - `opts` - Call options, see "Options" in the module docs
 
   ## Returns
 
   `{:ok, updated_catalog, namespace_ident}` - Success with NamespaceIdent struct
-  `{:error, updated_catalog, reason}` - Error with reason
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
 
   ## Examples
 
@@ -109,14 +227,58 @@ defmodule ExIceberg.Rest.Catalog do
       {:ok, catalog, created_ns} = ExIceberg.Rest.Catalog.create_namespace(catalog, ns, %{})
   """
   def create_namespace(
-        %__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %__MODULE__{} = catalog,
         %NamespaceIdent{} = namespace,
-        properties \\ %{}
+        properties \\ %{},
+        opts \\ []
       ) do
-    case Nif.rest_catalog_create_namespace(nif_catalog_resource, namespace, properties) do
-      {:ok, response} -> {:ok, catalog, response}
-      {:error, %NamespaceIdent{parts: [reason]}} -> {:error, catalog, reason}
-    end
+    ExIceberg.Catalog.create_namespace(catalog, namespace, properties, opts)
+  end
+
+  @doc """
+  Drops a namespace from the catalog.
+
+  ## Parameters
+
+  - `catalog` - The catalog struct
+  - `namespace` - NamespaceIdent struct
+  - `opts` - Call options, see "Options" in the module docs, and:
+
+    * `:cascade` - also drop the namespace's tables, views and nested
+      namespaces, defaults to `false`. Tables and views are dropped
+      concurrently, then the namespaces from the deepest up
+    * `:purge` - with `cascade: true`, purge the tables like
+      `drop_table/3` does, defaults to `false`
+    * `:dry_run` - with `cascade: true`, only list what would be dropped,
+      defaults to `false`
+
+  ## Returns
+
+  `{:ok, updated_catalog, namespace_ident}` - Success with dropped NamespaceIdent
+  `{:ok, updated_catalog, results}` - Success with `cascade: true`, a list of
+  maps with the object's `:kind` (`:table`, `:view` or `:namespace`), its
+  `:identifier`, its `:status` (`:dropped`, `:purged`, `:would_drop` or
+  `:failed`), the `:error` that failed it and the `:deleted_files` of tables
+  purged through FileIO. Objects that failed to drop do not fail the call,
+  failing to list the namespace's contents does
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
+
+  ## Examples
+
+      ns = NamespaceIdent.new("my_namespace")
+      {:ok, catalog, dropped_ns} = ExIceberg.Rest.Catalog.drop_namespace(catalog, ns)
+
+      # See what a cascading drop would remove, then drop everything
+      {:ok, catalog, results} =
+        ExIceberg.Rest.Catalog.drop_namespace(catalog, ns, cascade: true, dry_run: true)
+
+      {:ok, catalog, results} =
+        ExIceberg.Rest.Catalog.drop_namespace(catalog, ns, cascade: true, purge: true)
+
+      Enum.filter(results, &(&1.status == :failed))
+  """
+  def drop_namespace(%__MODULE__{} = catalog, %NamespaceIdent{} = namespace, opts \\ []) do
+    ExIceberg.Catalog.drop_namespace(catalog, namespace, opts)
   end
 
   @doc """
@@ -126,11 +288,12 @@ defmodule ExIceberg.Rest.Catalog do
 
   - `catalog` - The catalog struct
   - `table_ident` - TableIdent struct
+  - `opts` - Call options, see "Options" in the module docs
 
   ## Returns
 
   `{:ok, updated_catalog, exists}` - Success with boolean indicating if table exists
-  `{:error, updated_catalog, reason}` - Error with reason
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
 
   ## Examples
 
@@ -143,14 +306,8 @@ defmodule ExIceberg.Rest.Catalog do
       table_ident = TableIdent.new(namespace, "my_table")
       {:ok, catalog, exists} = ExIceberg.Rest.Catalog.table_exists?(catalog, table_ident)
   """
-  def table_exists?(
-        %__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog,
-        %TableIdent{} = table_ident
-      ) do
-    case Nif.rest_catalog_table_exists(nif_catalog_resource, table_ident) do
-      {:ok, exists} -> {:ok, catalog, exists}
-      {:error, _} -> {:error, catalog, "Failed to check table existence"}
-    end
+  def table_exists?(%__MODULE__{} = catalog, %TableIdent{} = table_ident, opts \\ []) do
+    ExIceberg.Catalog.table_exists?(catalog, table_ident, opts)
   end
 
   @doc """
@@ -160,11 +317,23 @@ defmodule ExIceberg.Rest.Catalog do
 
   - `catalog` - The catalog struct
   - `table_ident` - TableIdent struct
+  - `opts` - Call options, see "Options" in the module docs, and:
+
+    * `:purge` - also delete the table's data and metadata files, defaults
+      to `false`. REST catalogs are sent `purgeRequested=true` and delete the
+      files themselves, other catalogs drop the table and its files are then
+      deleted through the table's FileIO
 
   ## Returns
 
   `{:ok, updated_catalog, table_ident}` - Success with dropped TableIdent
-  `{:error, updated_catalog, reason}` - Error with reason
+  `{:ok, updated_catalog, report}` - Success with `purge: true`, a map with
+  the dropped `:table`, `:purged_by` (`:catalog` or `:file_io`) and
+  `:deleted_files`, the number of `:data_files`, `:delete_files`,
+  `:manifest_files`, `:manifest_lists` and `:metadata_files` deleted through
+  FileIO, not counting files already gone, e.g. deleted by the catalog when
+  dropping the table, `nil` when the catalog purged the table
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
 
   ## Examples
 
@@ -175,18 +344,13 @@ defmodule ExIceberg.Rest.Catalog do
       namespace = NamespaceIdent.new("my_namespace")
       table_ident = TableIdent.new(namespace, "my_table")
       {:ok, catalog, dropped_table} = ExIceberg.Rest.Catalog.drop_table(catalog, table_ident)
-  """
-  def drop_table(
-        %__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog,
-        %TableIdent{} = table_ident
-      ) do
-    case Nif.rest_catalog_drop_table(nif_catalog_resource, table_ident) do
-      {:ok, response} ->
-        {:ok, catalog, response}
 
-      {:error, %TableIdent{namespace: %NamespaceIdent{parts: [reason]}}} ->
-        {:error, catalog, reason}
-    end
+      # Delete the table's files too
+      {:ok, catalog, %{purged_by: :catalog}} =
+        ExIceberg.Rest.Catalog.drop_table(catalog, table_ident, purge: true)
+  """
+  def drop_table(%__MODULE__{} = catalog, %TableIdent{} = table_ident, opts \\ []) do
+    ExIceberg.Catalog.drop_table(catalog, table_ident, opts)
   end
 
   @doc """
@@ -198,11 +362,12 @@ defmodule ExIceberg.Rest.Catalog do
   - `table_ident` - TableIdent struct
   - `fields` - List of `ExIceberg.Types.Field` structs
   - `properties` - Map of table properties (optional)
+  - `opts` - Call options, see "Options" in the module docs
 
   ## Returns
 
   `{:ok, updated_catalog, table}` - Success with Table struct
-  `{:error, updated_catalog, reason}` - Error with reason
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
 
   ## Note
 
@@ -244,24 +409,13 @@ defmodule ExIceberg.Rest.Catalog do
       {:ok, catalog, table} = ExIceberg.Rest.Catalog.create_table(catalog, table_ident, fields, %{"owner" => "test"})
   """
   def create_table(
-        %__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %__MODULE__{} = catalog,
         %TableIdent{} = table_ident,
         fields,
-        properties \\ %{}
+        properties \\ %{},
+        opts \\ []
       ) do
-    case Nif.rest_catalog_create_table(
-           nif_catalog_resource,
-           table_ident,
-           fields,
-           properties
-         ) do
-      {:ok, table_resource} ->
-        table = ExIceberg.Table.new(table_resource)
-        {:ok, catalog, table}
-
-      {:error, reason} ->
-        {:error, catalog, reason}
-    end
+    ExIceberg.Catalog.create_table(catalog, table_ident, fields, properties, opts)
   end
 
   @doc """
@@ -271,11 +425,12 @@ defmodule ExIceberg.Rest.Catalog do
 
   - `catalog` - The catalog struct
   - `table_ident` - TableIdent struct
+  - `opts` - Call options, see "Options" in the module docs
 
   ## Returns
 
   `{:ok, updated_catalog, table}` - Success with Table struct
-  `{:error, updated_catalog, reason}` - Error with reason
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
 
   ## Examples
 
@@ -287,18 +442,40 @@ defmodule ExIceberg.Rest.Catalog do
       table_ident = TableIdent.new(namespace, "my_table")
       {:ok, catalog, table} = ExIceberg.Rest.Catalog.load_table(catalog, table_ident)
   """
-  def load_table(
-        %__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog,
-        %TableIdent{} = table_ident
-      ) do
-    case Nif.rest_catalog_load_table(nif_catalog_resource, table_ident) do
-      {:ok, table_resource} ->
-        table = ExIceberg.Table.new(table_resource)
-        {:ok, catalog, table}
+  def load_table(%__MODULE__{} = catalog, %TableIdent{} = table_ident, opts \\ []) do
+    ExIceberg.Catalog.load_table(catalog, table_ident, opts)
+  end
 
-      {:error, reason} ->
-        {:error, catalog, reason}
-    end
+  @doc """
+  Registers an existing table in the catalog from the location of its
+  metadata file, e.g. when moving tables between catalogs or recovering a
+  lost catalog.
+
+  ## Parameters
+
+  - `catalog` - The catalog struct
+  - `table_ident` - TableIdent struct the table is registered under
+  - `metadata_location` - Location of the table's `metadata.json` file
+  - `opts` - Call options, see "Options" in the module docs
+
+  ## Returns
+
+  `{:ok, updated_catalog, table}` - Success with Table struct
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
+
+  ## Examples
+
+      table_ident = TableIdent.from_string("my_namespace.my_table")
+      location = "s3://bucket/my_table/metadata/00001-a1b2.metadata.json"
+      {:ok, catalog, table} = ExIceberg.Rest.Catalog.register_table(catalog, table_ident, location)
+  """
+  def register_table(
+        %__MODULE__{} = catalog,
+        %TableIdent{} = table_ident,
+        metadata_location,
+        opts \\ []
+      ) do
+    ExIceberg.Catalog.register_table(catalog, table_ident, metadata_location, opts)
   end
 
   @doc """
@@ -309,11 +486,12 @@ defmodule ExIceberg.Rest.Catalog do
   - `catalog` - The catalog struct
   - `src_table_ident` - Source TableIdent struct
   - `dest_table_ident` - Destination TableIdent struct
+  - `opts` - Call options, see "Options" in the module docs
 
   ## Returns
 
   `{:ok, updated_catalog, response}` - Success with response map
-  `{:error, updated_catalog, reason}` - Error with reason
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
 
   ## Examples
 
@@ -329,17 +507,117 @@ defmodule ExIceberg.Rest.Catalog do
       {:ok, catalog, response} = ExIceberg.Rest.Catalog.rename_table(catalog, src_ident, dest_ident)
   """
   def rename_table(
-        %__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog,
+        %__MODULE__{} = catalog,
         %TableIdent{} = src_table_ident,
-        %TableIdent{} = dest_table_ident
+        %TableIdent{} = dest_table_ident,
+        opts \\ []
+      ) do
+    ExIceberg.Catalog.rename_table(catalog, src_table_ident, dest_table_ident, opts)
+  end
+
+  @doc """
+  Returns details of the catalog's current OAuth2 token.
+
+  ## Returns
+
+  `{:ok, catalog, info}` - `info` is a map with:
+
+    * `:expires_at` - `DateTime` at which the token expires, `nil` if unknown
+    * `:issued_token_type` - token type reported by a token exchange, if any
+    * `:refreshable` - whether `refresh_token/2` can request a new token
+
+  `info` is `nil` when no token was requested yet or the catalog has none.
+
+  ## Examples
+
+      {:ok, catalog, %{expires_at: expires_at}} = ExIceberg.Rest.Catalog.token_info(catalog)
+  """
+  def token_info(%__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog) do
+    info =
+      case Nif.rest_catalog_token_info(nif_catalog_resource) do
+        nil -> nil
+        info -> token_info_from_nif(info)
+      end
+
+    {:ok, catalog, info}
+  end
+
+  @doc """
+  Requests a new OAuth2 token without waiting for the current one to expire.
+
+  Requires a `:credential` or `:subject_token` in the catalog config. Tables
+  loaded from the catalog use the new token as well.
+
+  ## Parameters
+
+  - `catalog` - The catalog struct
+  - `opts` - Call options, see "Options" in the module docs
+
+  ## Returns
+
+  `{:ok, updated_catalog, info}` - Success with the new token's info, see `token_info/1`
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
+
+  ## Examples
+
+      {:ok, catalog, info} = ExIceberg.Rest.Catalog.refresh_token(catalog)
+  """
+  def refresh_token(
+        %__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog,
+        opts \\ []
       ) do
-    case Nif.rest_catalog_rename_table(
-           nif_catalog_resource,
-           src_table_ident,
-           dest_table_ident
-         ) do
-      {:ok, response} -> {:ok, catalog, response}
-      {:error, %{"error" => reason}} -> {:error, catalog, reason}
+    case Async.call(&Nif.rest_catalog_refresh_token(nif_catalog_resource, &1), opts) do
+      {:ok, info} -> {:ok, catalog, token_info_from_nif(info)}
+      {:error, %Error{} = error} -> {:error, catalog, error}
     end
   end
+
+  @doc """
+  Returns the catalog's configuration from the server's `/v1/config`
+  endpoint.
+
+  `new/2` does not contact the server, so this is also a way to check at
+  startup that the catalog is reachable and supports the operations a
+  service needs. The config is fetched once and kept for the catalog's
+  lifetime, as the catalog itself uses it to build its request URLs.
+
+  ## Parameters
+
+  - `catalog` - The catalog struct
+  - `opts` - Call options, see "Options" in the module docs
+
+  ## Returns
+
+  `{:ok, updated_catalog, config}` - `config` is a map with:
+
+    * `:defaults` - properties the client may override, e.g. `"prefix"`
+    * `:overrides` - properties that take precedence over the client's
+    * `:endpoints` - endpoints the server supports, as
+      `"GET /v1/{prefix}/namespaces/{namespace}/views"` strings, or `nil`
+      when the server does not advertise them, in which case only the
+      namespace and table endpoints can be assumed
+
+  `{:error, updated_catalog, error}` - Error with `ExIceberg.Error` struct
+
+  ## Examples
+
+      {:ok, catalog, %{endpoints: endpoints}} = ExIceberg.Rest.Catalog.config(catalog)
+
+      unless "POST /v1/{prefix}/transactions/commit" in (endpoints || []) do
+        raise "multi-table commits are not supported by this catalog"
+      end
+  """
+  def config(%__MODULE__{nif_catalog_resource: nif_catalog_resource} = catalog, opts \\ []) do
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.rest_catalog_config(nif_catalog_resource, identity, &1), opts) do
+      {:ok, config} -> {:ok, catalog, config}
+      {:error, %Error{} = error} -> {:error, catalog, error}
+    end
+  end
+
+  defp token_info_from_nif(%{expires_at: expires_at} = info) do
+    expires_at = if expires_at, do: DateTime.from_unix!(expires_at)
+    %{info | expires_at: expires_at}
+  end
 end
@@ -9,7 +9,24 @@ defmodule ExIceberg.Rest.CatalogConfig do
     :oauth2_server_uri,
     :scope,
     :audience,
-    :resource
+    :resource,
+    :connect_timeout,
+    :read_timeout,
+    :proxy,
+    :cacertfile,
+    :certfile,
+    :keyfile,
+    :max_retries,
+    :retry_base_delay,
+    :retry_max_delay,
+    :subject_token,
+    :subject_token_type,
+    :actor_token,
+    :actor_token_type,
+    :token_refresh_margin,
+    properties: %{},
+    headers: [],
+    file_io: %{}
   ]
 
   @type t :: %__MODULE__{
@@ -20,6 +37,23 @@ defmodule ExIceberg.Rest.CatalogConfig do
           oauth2_server_uri: String.t() | nil,
           scope: String.t() | nil,
           audience: String.t() | nil,
-          resource: String.t() | nil
+          resource: String.t() | nil,
+          connect_timeout: non_neg_integer() | nil,
+          read_timeout: non_neg_integer() | nil,
+          proxy: String.t() | nil,
+          cacertfile: Path.t() | nil,
+          certfile: Path.t() | nil,
+          keyfile: Path.t() | nil,
+          max_retries: non_neg_integer() | nil,
+          retry_base_delay: non_neg_integer() | nil,
+          retry_max_delay: non_neg_integer() | nil,
+          subject_token: String.t() | nil,
+          subject_token_type: String.t() | nil,
+          actor_token: String.t() | nil,
+          actor_token_type: String.t() | nil,
+          token_refresh_margin: non_neg_integer() | nil,
+          properties: %{optional(String.t()) => String.t()},
+          headers: [{String.t(), String.t()}],
+          file_io: %{optional(String.t()) => String.t()}
         }
 end
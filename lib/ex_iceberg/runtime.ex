@@ -0,0 +1,31 @@
+defmodule ExIceberg.Runtime do
+  @moduledoc """
+  Settings for the tokio runtime shared by all catalogs and tables.
+
+  Every catalog and table operation runs on a single process-wide runtime,
+  started on first use. It can be tuned through the application environment:
+
+      config :ex_iceberg, :runtime,
+        worker_threads: 4,
+        max_blocking_threads: 64,
+        thread_name: "ex_iceberg"
+
+    * `:worker_threads` - number of worker threads, defaults to the number of CPU cores
+    * `:max_blocking_threads` - upper limit of threads for blocking work, defaults to 512
+    * `:thread_name` - name given to the runtime threads, defaults to `"tokio-runtime-worker"`
+
+  The settings are read when the NIF is loaded, so they must be set before
+  the `:ex_iceberg` application starts.
+  """
+
+  @doc false
+  def load_data do
+    config = Application.get_env(:ex_iceberg, :runtime, [])
+
+    %{
+      worker_threads: config[:worker_threads],
+      max_blocking_threads: config[:max_blocking_threads],
+      thread_name: config[:thread_name]
+    }
+  end
+end
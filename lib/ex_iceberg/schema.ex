@@ -133,7 +133,7 @@ defmodule ExIceberg.Schema do
       Creates the table in the specified catalog using the defined schema.
 
       ## Parameters
-      - `catalog` - The catalog instance
+      - `catalog` - The catalog instance, e.g. `ExIceberg.Rest.Catalog` or `ExIceberg.Memory.Catalog`
       - `table_ident` - The TableIdent struct identifying the table
       - `properties` - Optional table properties (default: %{})
 
@@ -144,8 +144,12 @@ defmodule ExIceberg.Schema do
           table_ident = ExIceberg.TableIdent.from_string("my_namespace.my_table")
           {:ok, catalog, response} = MySchema.create_table(catalog, table_ident, %{"owner" => "team"})
       """
-      def create_table(catalog, %ExIceberg.TableIdent{} = table_ident, properties \\ %{}) do
-        ExIceberg.Rest.Catalog.create_table(
+      def create_table(
+            %catalog_module{} = catalog,
+            %ExIceberg.TableIdent{} = table_ident,
+            properties \\ %{}
+          ) do
+        catalog_module.create_table(
           catalog,
           table_ident,
           __fields__(),
@@ -0,0 +1,94 @@
+defmodule ExIceberg.Sql.Catalog do
+  @moduledoc """
+  SQL catalog implementation on SQLite using Rust NIFs.
+
+  Namespaces and table pointers are stored in a local SQLite database and
+  table files in a warehouse on the filesystem, so real Iceberg tables can be
+  kept without a catalog service, e.g. on edge devices or in CI jobs:
+
+      catalog =
+        ExIceberg.Sql.Catalog.new("local", %{
+          uri: "sqlite:///var/lib/iceberg/catalog.db?mode=rwc",
+          warehouse: "file:///var/lib/iceberg/warehouse"
+        })
+
+  Several catalogs can share a database, their tables are kept apart by the
+  catalog name.
+
+  ## Options
+
+  Functions that talk to the catalog accept a keyword list of options:
+
+    * `:timeout` - time in milliseconds to wait for the catalog, defaults to
+      `:infinity`. On timeout `%ExIceberg.Error{kind: :timeout}` is returned.
+
+  Tables loaded from this catalog do not support vended credentials or
+  remote signing.
+  """
+
+  alias ExIceberg.{Async, Error, Nif}
+  alias ExIceberg.Sql.CatalogConfig
+
+  defstruct name: nil, config: nil, nif_catalog_resource: nil
+
+  @type t :: %__MODULE__{
+          name: String.t(),
+          config: CatalogConfig.t(),
+          nif_catalog_resource: reference()
+        }
+
+  use ExIceberg.Catalog.Delegate,
+    register_table:
+      "Not supported by the SQL catalog yet, returns an error with kind `:not_supported`."
+
+  @doc """
+  Creates a new SQL catalog instance, creating the catalog tables in the
+  database if needed.
+
+  ## Parameters
+
+  - `name` - The name of the catalog, stored with its namespaces and tables
+  - `config` - Configuration map:
+
+    * `:uri` - SQLite database URI, add `?mode=rwc` to create the file if it
+      does not exist. In-memory databases are not shared between the pooled
+      connections, use a file instead
+    * `:warehouse` - location of the tables, e.g. `"file:///tmp/warehouse"`
+    * `:properties` - map of catalog properties, e.g. `"pool.max-connections"`
+      or `"pool.idle-timeout"` (in seconds)
+    * `:file_io` - map of FileIO properties, see `ExIceberg.FileIO`
+
+  Raises `ExIceberg.Error` if the database cannot be opened or the config is
+  invalid.
+
+  ## Examples
+
+      catalog =
+        ExIceberg.Sql.Catalog.new("local", %{
+          uri: "sqlite:///tmp/catalog.db?mode=rwc",
+          warehouse: "file:///tmp/warehouse"
+        })
+  """
+  def new(name, config) do
+    config = struct(CatalogConfig, config)
+
+    config = %{
+      config
+      | name: name,
+        properties: ExIceberg.FileIO.properties(config.properties),
+        file_io: ExIceberg.FileIO.properties(config.file_io)
+    }
+
+    nif_catalog_resource =
+      case Async.call(&Nif.sql_catalog_new(config, &1)) do
+        {:ok, nif_catalog_resource} -> nif_catalog_resource
+        {:error, %Error{} = error} -> raise error
+      end
+
+    %__MODULE__{
+      name: name,
+      config: config,
+      nif_catalog_resource: nif_catalog_resource
+    }
+  end
+end
@@ -0,0 +1,19 @@
+defmodule ExIceberg.Sql.CatalogConfig do
+  @moduledoc false
+
+  defstruct [
+    :name,
+    :uri,
+    :warehouse,
+    properties: %{},
+    file_io: %{}
+  ]
+
+  @type t :: %__MODULE__{
+          name: String.t(),
+          uri: String.t(),
+          warehouse: String.t(),
+          properties: %{optional(String.t()) => String.t()},
+          file_io: %{optional(String.t()) => String.t()}
+        }
+end
@@ -4,9 +4,20 @@ defmodule ExIceberg.Table do
 
   This module wraps the Rust Table implementation and provides Elixir-friendly
   access to table metadata and inspection capabilities.
+
+  A table holds the metadata loaded by the catalog operation that returned it.
+  All calls work against that snapshot until `refresh/1` or `invalidate_cache/1`
+  is used to pick up changes made elsewhere.
+
+  `from_metadata/2` opens a read-only table straight from a metadata file,
+  without a catalog. Such a static table keeps that metadata for good, it
+  cannot be refreshed and has no vended credentials.
+
+  Functions that may reach the catalog accept the same options as
+  `ExIceberg.Rest.Catalog`, such as `:timeout`.
   """
 
-  alias ExIceberg.Nif
+  alias ExIceberg.{Async, Nif}
   alias ExIceberg.Table.{MetadataTable}
 
   defstruct [:table_resource]
@@ -24,6 +35,60 @@ defmodule ExIceberg.Table do
     %__MODULE__{table_resource: table_resource}
   end
 
+  @doc """
+  Opens a read-only table from a `metadata.json` file, without a catalog.
+
+  ## Options
+
+    * `:file_io` - map of FileIO properties used to read the metadata and
+      the table files, see `ExIceberg.FileIO`
+    * `:identifier` - `ExIceberg.TableIdent` of the table, defaults to the
+      metadata location under the `static-table` namespace
+    * `:timeout` - time in milliseconds to wait, defaults to `:infinity`
+
+  ## Returns
+
+  `{:ok, table}` - Success with Table struct
+  `{:error, error}` - Error with `ExIceberg.Error` struct
+
+  ## Examples
+
+      {:ok, table} =
+        ExIceberg.Table.from_metadata(
+          "s3://bucket/orders/metadata/00003-a1b2.metadata.json",
+          file_io: %{"s3.region" => "eu-west-1"}
+        )
+  """
+  def from_metadata(metadata_location, opts \\ []) when is_binary(metadata_location) do
+    file_io = opts |> Keyword.get(:file_io, %{}) |> ExIceberg.FileIO.properties()
+    table_ident = Keyword.get(opts, :identifier)
+
+    case Async.call(
+           &Nif.static_table_from_metadata(metadata_location, table_ident, file_io, &1),
+           opts
+         ) do
+      {:ok, table_resource} -> {:ok, new(table_resource)}
+      {:error, %ExIceberg.Error{} = error} -> {:error, error}
+    end
+  end
+
+  @doc """
+  Returns the identifier of the table.
+
+  Namespace levels are kept as given to the catalog, including levels that
+  contain dots.
+
+  ## Examples
+
+      ExIceberg.Table.identifier(table)
+      # => #TableIdent<my_namespace.my_table>
+  """
+  @spec identifier(t()) :: ExIceberg.TableIdent.t()
+  def identifier(%__MODULE__{table_resource: table_resource})
+      when is_reference(table_resource) do
+    Nif.table_identifier(table_resource)
+  end
+
   @doc """
   Returns the table's metadata as a map.
 
@@ -47,8 +112,11 @@ defmodule ExIceberg.Table do
       #   "properties" => %{...}
       # }
   """
-  def metadata(%__MODULE__{table_resource: table_resource}) when is_reference(table_resource) do
-    case Nif.table_metadata(table_resource) do
+  def metadata(%__MODULE__{table_resource: table_resource}, opts \\ [])
+      when is_reference(table_resource) do
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.table_metadata(table_resource, identity, &1), opts) do
       {:ok, raw_metadata} ->
         # Parse JSON strings back to Elixir terms
         parsed_metadata = %{
@@ -63,8 +131,8 @@ defmodule ExIceberg.Table do
         # Return parsed metadata
         parsed_metadata
 
-      {:error, %{"error" => reason}} ->
-        raise "Failed to get table metadata: #{reason}"
+      {:error, %ExIceberg.Error{} = error} ->
+        raise error
     end
   end
 
@@ -78,9 +146,11 @@ defmodule ExIceberg.Table do
 
       metadata_ref = ExIceberg.Table.metadata_ref(table)
   """
-  def metadata_ref(%__MODULE__{table_resource: table_resource})
+  def metadata_ref(%__MODULE__{table_resource: table_resource}, opts \\ [])
       when is_reference(table_resource) do
-    case Nif.table_metadata_ref(table_resource) do
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.table_metadata_ref(table_resource, identity, &1), opts) do
       {:ok, raw_metadata} ->
         # Parse JSON strings back to Elixir terms
         %{
@@ -92,11 +162,69 @@ defmodule ExIceberg.Table do
           "properties" => Jason.decode!(raw_metadata["properties"])
         }
 
-      {:error, %{"error" => reason}} ->
-        raise "Failed to get table metadata reference: #{reason}"
+      {:error, %ExIceberg.Error{} = error} ->
+        raise error
     end
   end
 
+  @doc """
+  Reads the rows of the table's current snapshot, including static tables
+  opened with `from_metadata/2`.
+
+  Returns `{:ok, stream}`, a lazy stream of Arrow IPC stream binaries, one
+  per record batch, each of which can be read with e.g.
+  `Explorer.DataFrame.load_ipc_stream/2`. Batches are read from the data
+  files as the stream is consumed, so only the current one is held in
+  memory. A table without rows gives a single binary with the schema alone.
+
+  The stream reads the scan once, it cannot be enumerated again. Errors
+  while reading are raised as `ExIceberg.Error`.
+
+  For tables using remote signing, the data files are read with requests
+  signed by the catalog's signer.
+
+  ## Options
+
+    * `:columns` - list of column names to read, defaults to all columns
+    * `:token`, `:credential` or `:subject_token` - identity to load the
+      table and sign its requests as, instead of the catalog's
+    * `:timeout` - time in milliseconds to wait for the scan to start and
+      for each batch, defaults to `:infinity`
+
+  ## Examples
+
+      {:ok, stream} = ExIceberg.Table.scan(table, columns: ["id", "amount"])
+
+      stream
+      |> Stream.map(&Explorer.DataFrame.load_ipc_stream!/1)
+      |> Enum.to_list()
+      |> Explorer.DataFrame.concat_rows()
+  """
+  def scan(%__MODULE__{table_resource: table_resource}, opts \\ [])
+      when is_reference(table_resource) do
+    columns = Keyword.get(opts, :columns)
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.table_scan(table_resource, columns, identity, &1), opts) do
+      {:ok, scan_resource} -> {:ok, batches(scan_resource, opts)}
+      {:error, %ExIceberg.Error{}} = error -> error
+    end
+  end
+
+  defp batches(scan_resource, opts) do
+    Stream.resource(
+      fn -> scan_resource end,
+      fn scan_resource ->
+        case Async.call(&Nif.scan_next_batch(scan_resource, &1), opts) do
+          {:ok, nil} -> {:halt, scan_resource}
+          {:ok, ipc} -> {[ipc], scan_resource}
+          {:error, %ExIceberg.Error{} = error} -> raise error
+        end
+      end,
+      fn _scan_resource -> :ok end
+    )
+  end
+
   @doc """
   Returns a MetadataTable for inspecting table metadata.
 
@@ -108,23 +236,139 @@ defmodule ExIceberg.Table do
       metadata_table = ExIceberg.Table.inspect(table)
       # Returns a MetadataTable struct that can access snapshots, manifests, etc.
   """
-  def inspect(%__MODULE__{table_resource: table_resource}) when is_reference(table_resource) do
-    case Nif.table_inspect(table_resource) do
+  def inspect(%__MODULE__{table_resource: table_resource}, opts \\ [])
+      when is_reference(table_resource) do
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.table_inspect(table_resource, identity, &1), opts) do
       {:ok, inspect_data} ->
         # Get metadata for MetadataTable
-        metadata = metadata(%__MODULE__{table_resource: table_resource})
+        metadata = metadata(%__MODULE__{table_resource: table_resource}, opts)
         MetadataTable.new(inspect_data, metadata)
 
-      {:error, %{"error" => reason}} ->
-        raise "Failed to inspect table: #{reason}"
+      {:error, %ExIceberg.Error{} = error} ->
+        raise error
+    end
+  end
+
+  @doc """
+  Reloads the table from the catalog.
+
+  ## Returns
+
+  `:ok` - The table now holds the latest metadata
+  `{:error, error}` - Error with `ExIceberg.Error` struct, the previous metadata is kept,
+  `:not_supported` for static tables
+
+  ## Examples
+
+      :ok = ExIceberg.Table.refresh(table)
+      metadata = ExIceberg.Table.metadata(table)
+  """
+  def refresh(%__MODULE__{table_resource: table_resource}, opts \\ [])
+      when is_reference(table_resource) do
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.table_refresh(table_resource, identity, &1), opts) do
+      {:ok, :ok} -> :ok
+      {:error, %ExIceberg.Error{} = error} -> {:error, error}
+    end
+  end
+
+  @doc """
+  Returns the storage credentials the catalog vended for this table.
+
+  Catalogs vend credentials when asked to with the
+  `X-Iceberg-Access-Delegation: vended-credentials` header, see the `headers`
+  option of `ExIceberg.Rest.Catalog.new/2`. They are used by the table's file
+  access and renewed through the catalog's `/credentials` endpoint shortly
+  before they expire.
+
+  ## Returns
+
+  `%{config: map, expires_at: DateTime.t() | nil}` - The vended storage properties
+  `nil` - The catalog did not vend credentials for this table
+
+  ## Examples
+
+      %{config: config, expires_at: expires_at} = ExIceberg.Table.vended_credentials(table)
+      config["s3.access-key-id"]
+  """
+  def vended_credentials(%__MODULE__{table_resource: table_resource})
+      when is_reference(table_resource) do
+    table_resource
+    |> Nif.table_vended_credentials()
+    |> credentials_from_nif()
+  end
+
+  @doc """
+  Requests fresh storage credentials for this table from the catalog.
+
+  When the catalog returns no credentials for the table location, the table is
+  reloaded instead to pick up the credentials of the load-table response.
+  Either way, FileIO properties set in the catalog config, such as
+  `"s3.endpoint"`, keep precedence over the vended ones.
+
+  ## Returns
+
+  `{:ok, credentials}` - The new credentials, as returned by `vended_credentials/1`
+  `{:error, error}` - Error with `ExIceberg.Error` struct, the previous credentials are kept
+  """
+  def refresh_credentials(%__MODULE__{table_resource: table_resource}, opts \\ [])
+      when is_reference(table_resource) do
+    identity = Async.identity(opts)
+
+    case Async.call(&Nif.table_refresh_credentials(table_resource, identity, &1), opts) do
+      {:ok, credentials} -> {:ok, credentials_from_nif(credentials)}
+      {:error, %ExIceberg.Error{} = error} -> {:error, error}
     end
   end
 
   @doc """
-  Invalidates the metadata cache for this table.
+  Has the catalog sign an S3 request for this table's storage.
+
+  Catalogs using remote signing instead of vended credentials set
+  `s3.remote-signing-enabled` in the table config. Requests are then signed by
+  the signer at `s3.signer.uri` (the catalog by default) and `s3.signer.endpoint`
+  (`v1/aws/s3/sign` by default), authenticated like other catalog requests.
+
+  The table's own file access, e.g. `scan/2`, signs its requests this way.
+  Use this to sign requests sent with another S3 client.
+
+  ## Parameters
+
+  - `request` - Map with `:method`, `:uri`, `:headers` (header name to list of
+    values) and an optional `:body`
+
+  ## Returns
+
+  `{:ok, %{uri: uri, headers: headers}}` - The request to send, with signed headers
+  `{:error, error}` - Error with `ExIceberg.Error` struct, `:not_supported` when the
+  table does not use remote signing
+
+  ## Examples
+
+      request = %{
+        method: "GET",
+        uri: "http://localhost:9000/examples/data/file.parquet",
+        headers: %{"Host" => ["localhost:9000"]}
+      }
+
+      {:ok, %{uri: uri, headers: headers}} = ExIceberg.Table.sign_request(table, request)
+  """
+  def sign_request(%__MODULE__{table_resource: table_resource}, request, opts \\ [])
+      when is_reference(table_resource) and is_map(request) do
+    identity = Async.identity(opts)
+    request = Map.merge(%{headers: %{}, body: nil}, request)
+
+    Async.call(&Nif.table_sign_request(table_resource, request, identity, &1), opts)
+  end
+
+  @doc """
+  Invalidates the metadata held by this table.
 
   This forces the next metadata() call to fetch fresh data from the catalog.
-  The cache will remain invalidated until metadata is fetched again.
+  The table will remain invalidated until metadata is fetched again.
 
   Use this when you know the table metadata has changed and you need fresh data.
 
@@ -141,4 +385,11 @@ defmodule ExIceberg.Table do
     Nif.table_invalidate_cache(table_resource)
     :ok
   end
+
+  defp credentials_from_nif(nil), do: nil
+
+  defp credentials_from_nif(%{expires_at: expires_at} = credentials) do
+    expires_at = if expires_at, do: DateTime.from_unix!(expires_at)
+    %{credentials | expires_at: expires_at}
+  end
 end
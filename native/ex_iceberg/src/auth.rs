@@ -0,0 +1,402 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use reqwest::{Client, StatusCode};
+use rustler::{NifMap, NifTaggedEnum};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{ElixirError, ElixirErrorKind};
+
+const CLIENT_CREDENTIALS_GRANT: &str = "client_credentials";
+const TOKEN_EXCHANGE_GRANT: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+pub const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+// Identity used for a single request instead of the catalog's own, given as
+// `{:token, token}`, `{:credential, "client_id:client_secret"}` or
+// `{:subject_token, token}`, exchanged for a token with RFC 8693
+#[derive(Debug, Clone, NifTaggedEnum)]
+pub enum Identity {
+    Token(String),
+    Credential(String),
+    SubjectToken(String),
+}
+
+impl Identity {
+    /// Key of the identity's cached session, distinct for each kind.
+    pub fn cache_key(&self) -> String {
+        match self {
+            Identity::Token(token) => format!("token:{}", token),
+            Identity::Credential(credential) => format!("credential:{}", credential),
+            Identity::SubjectToken(token) => format!("subject_token:{}", token),
+        }
+    }
+}
+
+// Settings of the OAuth2 token endpoint grant used by the REST catalog
+#[derive(Debug, Default, Clone)]
+pub struct OAuth2Config {
+    pub token_endpoint: String,
+    // `client_id:client_secret` or just `client_secret`, as in iceberg-rust
+    pub credential: Option<String>,
+    pub scope: Option<String>,
+    pub audience: Option<String>,
+    pub resource: Option<String>,
+    // RFC 8693 token exchange, used instead of client credentials when set
+    pub subject_token: Option<String>,
+    pub subject_token_type: Option<String>,
+    pub actor_token: Option<String>,
+    pub actor_token_type: Option<String>,
+    // Tokens are refreshed this long before they expire
+    pub refresh_margin: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub access_token: String,
+    pub expires_at: Option<SystemTime>,
+    pub issued_token_type: Option<String>,
+}
+
+// Token details returned to Elixir, never the token itself
+#[derive(Debug, NifMap)]
+pub struct TokenInfo {
+    // Unix time in seconds, nil when the expiry is unknown
+    pub expires_at: Option<u64>,
+    pub issued_token_type: Option<String>,
+    pub refreshable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+    issued_token_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+// OAuth2 session of a REST catalog. Fetches a token on first use and again
+// when it is about to expire, the catalog is then rebuilt with the new token.
+pub struct OAuth2Session {
+    config: OAuth2Config,
+    client: Client,
+    current: Mutex<Option<Token>>,
+    // Serialises token requests so concurrent calls share one refresh
+    fetch_lock: tokio::sync::Mutex<()>,
+}
+
+impl OAuth2Session {
+    pub fn new(config: OAuth2Config, client: Client) -> Self {
+        Self {
+            config,
+            client,
+            current: Mutex::new(None),
+            fetch_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn set_token(&self, token: Token) {
+        *self.current.lock().unwrap_or_else(PoisonError::into_inner) = Some(token);
+    }
+
+    pub fn current(&self) -> Option<Token> {
+        self.current
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns a valid token, requesting a new one when there is none yet or
+    /// the current one is about to expire.
+    pub async fn token(&self) -> Result<Token, ElixirError> {
+        if let Some(token) = self.usable_token() {
+            return Ok(token);
+        }
+
+        let _guard = self.fetch_lock.lock().await;
+
+        // Another call may have refreshed the token while this one waited
+        match self.usable_token() {
+            Some(token) => Ok(token),
+            None => self.fetch().await,
+        }
+    }
+
+    /// Requests a new token even if the current one is still valid.
+    pub async fn refresh(&self) -> Result<Token, ElixirError> {
+        let _guard = self.fetch_lock.lock().await;
+        self.fetch().await
+    }
+
+    fn usable_token(&self) -> Option<Token> {
+        let token = self.current()?;
+
+        match token.expires_at {
+            Some(expires_at) if expires_at <= SystemTime::now() + self.config.refresh_margin => {
+                None
+            }
+            _ => Some(token),
+        }
+    }
+
+    async fn fetch(&self) -> Result<Token, ElixirError> {
+        let response = self
+            .client
+            .post(&self.config.token_endpoint)
+            .form(&self.config.grant_params())
+            .send()
+            .await
+            .map_err(|e| ElixirError {
+                source: Some(e.to_string()),
+                ..ElixirError::new(
+                    ElixirErrorKind::Unexpected,
+                    "Failed to request OAuth2 token",
+                )
+            })?;
+
+        let status = response.status();
+        let body = response.bytes().await.map_err(|e| {
+            ElixirError::new(
+                ElixirErrorKind::Unexpected,
+                format!("Failed to read OAuth2 token response: {}", e),
+            )
+        })?;
+
+        if status != StatusCode::OK {
+            return Err(token_error(status, &body));
+        }
+
+        let response: TokenResponse = serde_json::from_slice(&body).map_err(|e| {
+            ElixirError::new(
+                ElixirErrorKind::DataInvalid,
+                format!("Invalid OAuth2 token response: {}", e),
+            )
+        })?;
+
+        let expires_at = response
+            .expires_in
+            .map(|expires_in| SystemTime::now() + Duration::from_secs(expires_in))
+            .or_else(|| jwt_expiry(&response.access_token));
+
+        let token = Token {
+            access_token: response.access_token,
+            expires_at,
+            issued_token_type: response.issued_token_type,
+        };
+
+        self.set_token(token.clone());
+        Ok(token)
+    }
+}
+
+impl OAuth2Config {
+    /// Whether tokens can be requested, rather than only given in the config.
+    pub fn has_grant(&self) -> bool {
+        self.credential.is_some() || self.subject_token.is_some()
+    }
+
+    fn grant_params(&self) -> HashMap<&'static str, String> {
+        let mut params = HashMap::new();
+
+        if let Some(credential) = &self.credential {
+            match credential.split_once(':') {
+                Some((client_id, client_secret)) => {
+                    params.insert("client_id", client_id.to_string());
+                    params.insert("client_secret", client_secret.to_string());
+                }
+                None => {
+                    params.insert("client_secret", credential.to_string());
+                }
+            }
+        }
+
+        match &self.subject_token {
+            Some(subject_token) => {
+                params.insert("grant_type", TOKEN_EXCHANGE_GRANT.to_string());
+                params.insert("subject_token", subject_token.to_string());
+                params.insert(
+                    "subject_token_type",
+                    self.subject_token_type
+                        .clone()
+                        .unwrap_or_else(|| ACCESS_TOKEN_TYPE.to_string()),
+                );
+
+                if let Some(actor_token) = &self.actor_token {
+                    params.insert("actor_token", actor_token.to_string());
+                    params.insert(
+                        "actor_token_type",
+                        self.actor_token_type
+                            .clone()
+                            .unwrap_or_else(|| ACCESS_TOKEN_TYPE.to_string()),
+                    );
+                }
+            }
+            None => {
+                params.insert("grant_type", CLIENT_CREDENTIALS_GRANT.to_string());
+            }
+        }
+
+        // Same default scope as the iceberg-rust REST catalog
+        params.insert(
+            "scope",
+            self.scope.clone().unwrap_or_else(|| "catalog".to_string()),
+        );
+
+        if let Some(audience) = &self.audience {
+            params.insert("audience", audience.to_string());
+        }
+
+        if let Some(resource) = &self.resource {
+            params.insert("resource", resource.to_string());
+        }
+
+        params
+    }
+}
+
+impl Token {
+    pub fn info(&self, refreshable: bool) -> TokenInfo {
+        TokenInfo {
+            expires_at: self
+                .expires_at
+                .and_then(|expires_at| expires_at.duration_since(UNIX_EPOCH).ok())
+                .map(|expires_at| expires_at.as_secs()),
+            issued_token_type: self.issued_token_type.clone(),
+            refreshable,
+        }
+    }
+
+    /// A token given directly in the config, its expiry is read from the JWT
+    /// `exp` claim when it has one.
+    pub fn from_static(access_token: String) -> Self {
+        Self {
+            expires_at: jwt_expiry(&access_token),
+            access_token,
+            issued_token_type: None,
+        }
+    }
+}
+
+fn token_error(status: StatusCode, body: &[u8]) -> ElixirError {
+    let message = match serde_json::from_slice::<TokenErrorResponse>(body) {
+        Ok(TokenErrorResponse {
+            error,
+            error_description: Some(description),
+        }) => format!("{}: {}", error, description),
+        Ok(TokenErrorResponse { error, .. }) => error,
+        Err(_) => String::from_utf8_lossy(body).to_string(),
+    };
+
+    let kind = match status {
+        StatusCode::FORBIDDEN => ElixirErrorKind::Forbidden,
+        StatusCode::SERVICE_UNAVAILABLE => ElixirErrorKind::ServiceUnavailable,
+        _ => ElixirErrorKind::Unauthorized,
+    };
+
+    ElixirError {
+        http_status: Some(status.as_u16()),
+        ..ElixirError::new(kind, format!("Failed to request OAuth2 token: {}", message))
+    }
+}
+
+// Reads the `exp` claim without verifying the token, it is only used to
+// schedule refreshes.
+fn jwt_expiry(token: &str) -> Option<SystemTime> {
+    let payload = token.split('.').nth(1)?;
+    let claims: serde_json::Value =
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+    let exp = claims.get("exp")?.as_u64()?;
+
+    Some(UNIX_EPOCH + Duration::from_secs(exp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OAuth2Config {
+        OAuth2Config {
+            token_endpoint: "http://localhost:8080/token".to_string(),
+            credential: Some("client:secret".to_string()),
+            scope: None,
+            audience: None,
+            resource: None,
+            subject_token: None,
+            subject_token_type: None,
+            actor_token: None,
+            actor_token_type: None,
+            refresh_margin: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn client_credentials_params() {
+        let params = config().grant_params();
+
+        assert_eq!(params["grant_type"], "client_credentials");
+        assert_eq!(params["client_id"], "client");
+        assert_eq!(params["client_secret"], "secret");
+        assert_eq!(params["scope"], "catalog");
+    }
+
+    #[test]
+    fn token_exchange_params() {
+        let config = OAuth2Config {
+            subject_token: Some("user-token".to_string()),
+            audience: Some("lakekeeper".to_string()),
+            ..config()
+        };
+
+        let params = config.grant_params();
+
+        assert_eq!(params["grant_type"], TOKEN_EXCHANGE_GRANT);
+        assert_eq!(params["subject_token"], "user-token");
+        assert_eq!(params["subject_token_type"], ACCESS_TOKEN_TYPE);
+        assert_eq!(params["audience"], "lakekeeper");
+        assert!(!params.contains_key("actor_token"));
+    }
+
+    #[test]
+    fn reads_jwt_expiry() {
+        let payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"user","exp":1700000000}"#);
+        let token = format!("header.{}.signature", payload);
+
+        let info = Token::from_static(token).info(false);
+
+        assert_eq!(info.expires_at, Some(1_700_000_000));
+        assert!(jwt_expiry("opaque-token").is_none());
+    }
+
+    #[test]
+    fn refreshes_expiring_token() {
+        let session = OAuth2Session::new(config(), Client::new());
+        *session.current.lock().unwrap() = Some(Token {
+            access_token: "token".to_string(),
+            expires_at: Some(SystemTime::now() + Duration::from_secs(30)),
+            issued_token_type: None,
+        });
+
+        // Expires within the 60s margin
+        assert!(session.usable_token().is_none());
+    }
+
+    #[test]
+    fn maps_token_errors() {
+        let error = token_error(
+            StatusCode::UNAUTHORIZED,
+            br#"{"error":"invalid_client","error_description":"Invalid client credentials"}"#,
+        );
+
+        assert_eq!(error.kind, ElixirErrorKind::Unauthorized);
+        assert_eq!(error.http_status, Some(401));
+        assert!(error
+            .message
+            .ends_with("invalid_client: Invalid client credentials"));
+    }
+}
@@ -0,0 +1,543 @@
+use rustler::{NifMap, NifUnitEnum, NifUntaggedEnum};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Deref;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use iceberg::{Catalog, NamespaceIdent, TableIdent};
+
+use crate::auth::Identity;
+use crate::catalog::CatalogResource;
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::purge::{self, DeletedFiles};
+use crate::types::{ElixirNamespaceIdent, ElixirTableIdent};
+
+// Tables and views dropped at once by a cascading drop
+const DROP_CONCURRENCY: usize = 8;
+
+// Encoded as `:table`, `:view` or `:namespace`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, NifUnitEnum)]
+pub enum ObjectKind {
+    Table,
+    View,
+    Namespace,
+}
+
+// Encoded as `:dropped`, `:purged`, `:would_drop` or `:failed`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, NifUnitEnum)]
+pub enum DropStatus {
+    Dropped,
+    Purged,
+    // Dry runs only list the objects
+    WouldDrop,
+    Failed,
+}
+
+#[derive(Debug, NifUntaggedEnum)]
+pub enum ObjectIdent {
+    Table(ElixirTableIdent),
+    Namespace(ElixirNamespaceIdent),
+}
+
+#[derive(Debug, NifMap)]
+pub struct DropResult {
+    pub kind: ObjectKind,
+    pub identifier: ObjectIdent,
+    pub status: DropStatus,
+    pub error: Option<ElixirError>,
+    // Only known for tables purged through FileIO
+    pub deleted_files: Option<DeletedFiles>,
+}
+
+// Status of a dropped object, with the files deleted by a FileIO purge
+type Outcome = Result<(DropStatus, Option<DeletedFiles>), ElixirError>;
+
+// Everything under a namespace, found before anything is dropped
+#[derive(Debug, Default)]
+struct Plan {
+    tables: Vec<TableIdent>,
+    views: Vec<TableIdent>,
+    // Children before their parents
+    namespaces: Vec<NamespaceIdent>,
+}
+
+/// Drops a namespace with its tables, views and nested namespaces, purging
+/// the tables with `purge`. Tables and views are dropped in parallel, each in
+/// its own task on the runtime, then the namespaces from the deepest up.
+///
+/// Listing failures fail the whole drop, other failures are reported per
+/// object. A `dry_run` returns the objects that would be dropped.
+pub async fn drop_namespace<R>(
+    catalog_resource: R,
+    namespace: &NamespaceIdent,
+    purge: bool,
+    dry_run: bool,
+    identity: Option<Identity>,
+) -> Result<Vec<DropResult>, ElixirError>
+where
+    R: Deref<Target = CatalogResource> + Clone + Send + Sync + 'static,
+{
+    let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+    let plan = plan(
+        &catalog_resource,
+        catalog.as_ref(),
+        namespace,
+        identity.as_ref(),
+    )
+    .await?;
+
+    if dry_run {
+        let tables = plan
+            .tables
+            .into_iter()
+            .map(|t| (ObjectKind::Table, t.into()));
+        let views = plan.views.into_iter().map(|v| (ObjectKind::View, v.into()));
+        let namespaces = plan
+            .namespaces
+            .into_iter()
+            .map(|namespace| (ObjectKind::Namespace, namespace.into()));
+
+        return Ok(tables
+            .chain(views)
+            .chain(namespaces)
+            .map(|(kind, ident)| DropResult::new(kind, ident, Ok((DropStatus::WouldDrop, None))))
+            .collect());
+    }
+
+    let tables = plan.tables.into_iter().map(|t| (ObjectKind::Table, t));
+    let views = plan.views.into_iter().map(|v| (ObjectKind::View, v));
+
+    // The tasks are aborted when the set is dropped, e.g. when the drop times
+    // out, and wait for a permit so at most DROP_CONCURRENCY run at once
+    let permits = Arc::new(Semaphore::new(DROP_CONCURRENCY));
+    let mut drops = JoinSet::new();
+    let mut objects = HashMap::new();
+
+    for (kind, ident) in tables.chain(views) {
+        let catalog_resource = catalog_resource.clone();
+        let catalog = catalog.clone();
+        let identity = identity.clone();
+        let permits = permits.clone();
+        let task_ident = ident.clone();
+
+        let handle = drops.spawn(async move {
+            // The semaphore is never closed
+            let _permit = permits.acquire_owned().await;
+            let outcome = drop_object(
+                &catalog_resource,
+                catalog.as_ref(),
+                kind,
+                &task_ident,
+                purge,
+                identity.as_ref(),
+            )
+            .await;
+            (kind, task_ident, outcome)
+        });
+        // Only needed to report tasks that panicked
+        objects.insert(handle.id(), (kind, ident));
+    }
+
+    let mut results = Vec::with_capacity(objects.len());
+    while let Some(joined) = drops.join_next().await {
+        let (kind, ident, outcome) = match joined {
+            Ok(dropped) => dropped,
+            Err(e) => {
+                let (kind, ident) = objects.remove(&e.id()).ok_or_else(|| {
+                    ElixirError::new(
+                        ElixirErrorKind::Unexpected,
+                        format!("Drop task failed for an unknown object: {}", e),
+                    )
+                })?;
+                let error = ElixirError::new(
+                    ElixirErrorKind::Unexpected,
+                    format!("Drop task failed: {}", e),
+                );
+                (kind, ident, Err(error))
+            }
+        };
+        results.push(DropResult::new(kind, ident.into(), outcome));
+    }
+
+    // A namespace can only be dropped once its children are gone
+    for namespace in plan.namespaces {
+        let outcome = catalog_resource
+            .retry()
+            .run(false, "Failed to drop namespace", || {
+                catalog.drop_namespace(&namespace)
+            })
+            .await
+            .map(|_| (DropStatus::Dropped, None));
+
+        results.push(DropResult::new(
+            ObjectKind::Namespace,
+            namespace.into(),
+            outcome,
+        ));
+    }
+
+    Ok(results)
+}
+
+async fn drop_object(
+    catalog_resource: &CatalogResource,
+    catalog: &dyn Catalog,
+    kind: ObjectKind,
+    ident: &TableIdent,
+    purge: bool,
+    identity: Option<&Identity>,
+) -> Outcome {
+    match kind {
+        ObjectKind::View => drop_view(catalog_resource, ident, identity).await,
+        _ if purge => purge::purge_table(catalog_resource, ident, identity)
+            .await
+            .map(|report| (DropStatus::Purged, report.deleted_files)),
+        _ => catalog_resource
+            .retry()
+            .run(false, "Failed to drop table", || catalog.drop_table(ident))
+            .await
+            .map(|_| (DropStatus::Dropped, None)),
+    }
+}
+
+async fn plan(
+    catalog_resource: &CatalogResource,
+    catalog: &dyn Catalog,
+    root: &NamespaceIdent,
+    identity: Option<&Identity>,
+) -> Result<Plan, ElixirError> {
+    let mut plan = Plan::default();
+    let mut pending = VecDeque::from([root.clone()]);
+    let mut seen = HashSet::from([root.clone()]);
+
+    while let Some(namespace) = pending.pop_front() {
+        let children = match catalog_resource
+            .retry()
+            .run(true, "Failed to list namespaces", || {
+                catalog.list_namespaces(Some(&namespace))
+            })
+            .await
+        {
+            Ok(children) => children,
+            // Nested namespaces dropped meanwhile, or wrongly listed, see below
+            Err(e) if e.kind == ElixirErrorKind::NoSuchNamespace && &namespace != root => continue,
+            Err(e) => return Err(e),
+        };
+
+        for child in children {
+            if let Some(child) = child_namespace(&namespace, child) {
+                if seen.insert(child.clone()) {
+                    pending.push_back(child);
+                }
+            }
+        }
+
+        let tables = catalog_resource
+            .retry()
+            .run(true, "Failed to list tables", || {
+                catalog.list_tables(&namespace)
+            })
+            .await?;
+        plan.tables.extend(tables);
+        plan.views
+            .extend(list_views(catalog_resource, &namespace, identity).await?);
+        plan.namespaces.push(namespace);
+    }
+
+    plan.namespaces
+        .sort_by_key(|namespace| Reverse(namespace.len()));
+    Ok(plan)
+}
+
+// Full identifier of a namespace listed under `parent`. The memory catalog
+// lists nested namespaces by their own name, the SQL catalog by their full
+// identifier, including every descendant and the siblings whose name starts
+// with the parent's.
+fn child_namespace(parent: &NamespaceIdent, child: NamespaceIdent) -> Option<NamespaceIdent> {
+    if child.len() > parent.len() {
+        return child.starts_with(parent).then_some(child);
+    }
+
+    NamespaceIdent::from_vec(parent.iter().chain(child.iter()).cloned().collect()).ok()
+}
+
+// Only REST catalogs have views, servers without view support answer 404
+async fn list_views(
+    catalog_resource: &CatalogResource,
+    namespace: &NamespaceIdent,
+    identity: Option<&Identity>,
+) -> Result<Vec<TableIdent>, ElixirError> {
+    let Some(rest) = catalog_resource.rest() else {
+        return Ok(Vec::new());
+    };
+
+    match rest.list_views(namespace, identity).await {
+        Err(e)
+            if matches!(
+                e.kind,
+                ElixirErrorKind::NotFound | ElixirErrorKind::NotSupported
+            ) =>
+        {
+            Ok(Vec::new())
+        }
+        result => result,
+    }
+}
+
+async fn drop_view(
+    catalog_resource: &CatalogResource,
+    view_ident: &TableIdent,
+    identity: Option<&Identity>,
+) -> Outcome {
+    match catalog_resource.rest() {
+        Some(rest) => rest
+            .drop_view(view_ident, identity)
+            .await
+            .map(|_| (DropStatus::Dropped, None)),
+        None => Err(ElixirError::new(
+            ElixirErrorKind::NotSupported,
+            "Views are only supported by REST catalogs",
+        )),
+    }
+}
+
+impl DropResult {
+    fn new(kind: ObjectKind, identifier: ObjectIdent, outcome: Outcome) -> Self {
+        let (status, error, deleted_files) = match outcome {
+            Ok((status, deleted_files)) => (status, None, deleted_files),
+            Err(e) => (DropStatus::Failed, Some(e), None),
+        };
+
+        Self {
+            kind,
+            identifier,
+            status,
+            error,
+            deleted_files,
+        }
+    }
+}
+
+impl From<TableIdent> for ObjectIdent {
+    fn from(ident: TableIdent) -> Self {
+        ObjectIdent::Table(ident.into())
+    }
+}
+
+impl From<NamespaceIdent> for ObjectIdent {
+    fn from(namespace: NamespaceIdent) -> Self {
+        ObjectIdent::Namespace(namespace.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::sql_catalog;
+    use crate::test_support;
+
+    fn namespace(parts: &[&str]) -> NamespaceIdent {
+        NamespaceIdent::from_strs(parts).unwrap()
+    }
+
+    fn summary(results: &[DropResult]) -> Vec<(ObjectKind, String, DropStatus)> {
+        let mut summary = results
+            .iter()
+            .map(|result| {
+                let name = match &result.identifier {
+                    ObjectIdent::Table(table) => {
+                        format!("{}.{}", table.namespace.parts.join("."), table.name)
+                    }
+                    ObjectIdent::Namespace(namespace) => namespace.parts.join("."),
+                };
+                (result.kind, name, result.status)
+            })
+            .collect::<Vec<_>>();
+        summary.sort_by(|a, b| a.1.cmp(&b.1));
+        summary
+    }
+
+    #[test]
+    fn drops_nested_namespaces_and_tables() {
+        let catalog_resource = Arc::new(test_support::memory_catalog());
+
+        let runtime = runtime().unwrap();
+        runtime.block_on(async {
+            for (parts, table) in [(&["db"][..], "orders"), (&["db", "staging"], "events")] {
+                let table_ident = TableIdent::new(namespace(parts), table.to_string());
+                test_support::create_table(&catalog_resource, &table_ident).await;
+            }
+        });
+
+        let expected = |table_status, namespace_status| {
+            vec![
+                (ObjectKind::Namespace, "db".to_string(), namespace_status),
+                (ObjectKind::Table, "db.orders".to_string(), table_status),
+                (
+                    ObjectKind::Namespace,
+                    "db.staging".to_string(),
+                    namespace_status,
+                ),
+                (
+                    ObjectKind::Table,
+                    "db.staging.events".to_string(),
+                    table_status,
+                ),
+            ]
+        };
+
+        let results = runtime
+            .block_on(drop_namespace(
+                catalog_resource.clone(),
+                &namespace(&["db"]),
+                true,
+                true,
+                None,
+            ))
+            .unwrap();
+        assert_eq!(
+            summary(&results),
+            expected(DropStatus::WouldDrop, DropStatus::WouldDrop)
+        );
+
+        let results = runtime
+            .block_on(drop_namespace(
+                catalog_resource.clone(),
+                &namespace(&["db"]),
+                true,
+                false,
+                None,
+            ))
+            .unwrap();
+        assert_eq!(
+            summary(&results),
+            expected(DropStatus::Purged, DropStatus::Dropped)
+        );
+        assert!(results.iter().all(|result| result.error.is_none()));
+
+        let exists = runtime.block_on(async {
+            let catalog = catalog_resource.catalog(None).await.unwrap();
+            catalog.namespace_exists(&namespace(&["db"])).await
+        });
+        assert!(!exists.unwrap());
+    }
+
+    // Drops `db.staging`, next to a sibling whose name starts with it
+    fn drops_only_nested_namespaces(catalog_resource: CatalogResource) {
+        let catalog_resource = Arc::new(catalog_resource);
+        let tables = [
+            (&["db"][..], "orders"),
+            (&["db", "staging"], "events"),
+            (&["db", "staging", "raw"], "clicks"),
+            (&["db", "staging_old"], "archive"),
+        ];
+
+        let runtime = runtime().unwrap();
+        runtime.block_on(async {
+            for (parts, table) in tables {
+                let table_ident = TableIdent::new(namespace(parts), table.to_string());
+                test_support::create_table(&catalog_resource, &table_ident).await;
+            }
+        });
+
+        let results = runtime
+            .block_on(drop_namespace(
+                catalog_resource.clone(),
+                &namespace(&["db", "staging"]),
+                false,
+                false,
+                None,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            summary(&results),
+            vec![
+                (
+                    ObjectKind::Namespace,
+                    "db.staging".to_string(),
+                    DropStatus::Dropped
+                ),
+                (
+                    ObjectKind::Table,
+                    "db.staging.events".to_string(),
+                    DropStatus::Dropped
+                ),
+                (
+                    ObjectKind::Namespace,
+                    "db.staging.raw".to_string(),
+                    DropStatus::Dropped
+                ),
+                (
+                    ObjectKind::Table,
+                    "db.staging.raw.clicks".to_string(),
+                    DropStatus::Dropped
+                ),
+            ]
+        );
+
+        let remaining = runtime.block_on(async {
+            let catalog = catalog_resource.catalog(None).await.unwrap();
+            let mut remaining = Vec::new();
+            for parts in [&["db"][..], &["db", "staging_old"]] {
+                remaining.extend(catalog.list_tables(&namespace(parts)).await.unwrap());
+            }
+            remaining
+        });
+        assert_eq!(
+            remaining
+                .iter()
+                .map(|table| table.name())
+                .collect::<Vec<_>>(),
+            ["orders", "archive"]
+        );
+    }
+
+    // Lists nested namespaces by their own name
+    #[test]
+    fn drops_nested_memory_namespaces() {
+        drops_only_nested_namespaces(test_support::memory_catalog());
+    }
+
+    // Lists nested namespaces by their full identifier, with the descendants
+    // and the siblings starting with the parent's name
+    #[test]
+    fn drops_nested_sql_namespaces() {
+        let dir = std::env::temp_dir().join(format!("ex_iceberg_cascade_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let uri = format!("sqlite://{}/catalog.db?mode=rwc", dir.display());
+
+        let catalog_resource = runtime()
+            .unwrap()
+            .block_on(sql_catalog::load(test_support::props(&[
+                ("name", "test"),
+                ("uri", &uri),
+                ("warehouse", "memory://warehouse"),
+            ])))
+            .unwrap();
+
+        drops_only_nested_namespaces(catalog_resource);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn nested_namespaces_get_full_identifiers() {
+        let parent = namespace(&["db", "staging"]);
+
+        assert_eq!(
+            child_namespace(&parent, namespace(&["events"])),
+            Some(namespace(&["db", "staging", "events"]))
+        );
+        assert_eq!(
+            child_namespace(&parent, namespace(&["db", "staging", "events"])),
+            Some(namespace(&["db", "staging", "events"]))
+        );
+        assert_eq!(
+            child_namespace(&parent, namespace(&["db", "staging_old", "events"])),
+            None
+        );
+    }
+}
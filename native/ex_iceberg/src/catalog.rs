@@ -1,19 +1,36 @@
-use rustler::{Atom, NifStruct, NifTaggedEnum, ResourceArc};
+use rustler::{Env, ResourceArc, Term};
 use std::collections::HashMap;
+use std::panic::RefUnwindSafe;
 use std::sync::Arc;
-use tokio::runtime::Runtime;
 
 use iceberg::spec::{ListType, MapType, NestedField, PrimitiveType, Schema, StructType, Type};
-use iceberg::{Catalog, CatalogBuilder, NamespaceIdent, TableCreation, TableIdent};
-use iceberg_catalog_rest::{RestCatalog, RestCatalogBuilder};
-
-use crate::atoms;
+use iceberg::table::Table;
+use iceberg::{Catalog, NamespaceIdent, TableCreation, TableIdent};
+
+use crate::auth::Identity;
+use crate::cascade;
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::glue_catalog;
+use crate::hms_catalog;
+use crate::memory_catalog;
+use crate::purge;
+use crate::rest_catalog::{self, RestCatalogResource};
+use crate::retry::RetryPolicy;
+use crate::sql_catalog;
 use crate::table::SmartTableResource;
+use crate::task::{reply_async, AsyncReply};
 use crate::types::{ElixirNamespaceIdent, ElixirTableIdent, IcebergField, IcebergFieldType};
 
-impl From<ElixirNamespaceIdent> for NamespaceIdent {
-    fn from(elixir_ns: ElixirNamespaceIdent) -> Self {
-        NamespaceIdent::from_vec(elixir_ns.parts).expect("Invalid namespace")
+impl TryFrom<ElixirNamespaceIdent> for NamespaceIdent {
+    type Error = ElixirError;
+
+    fn try_from(elixir_ns: ElixirNamespaceIdent) -> Result<Self, Self::Error> {
+        NamespaceIdent::from_vec(elixir_ns.parts).map_err(|e| {
+            ElixirError::new(
+                ElixirErrorKind::InvalidArgument,
+                format!("Invalid namespace: {}", e.message()),
+            )
+        })
     }
 }
 
@@ -23,9 +40,21 @@ impl From<NamespaceIdent> for ElixirNamespaceIdent {
     }
 }
 
-impl From<ElixirTableIdent> for TableIdent {
-    fn from(elixir_table: ElixirTableIdent) -> Self {
-        TableIdent::new(elixir_table.namespace.into(), elixir_table.name)
+impl TryFrom<ElixirTableIdent> for TableIdent {
+    type Error = ElixirError;
+
+    fn try_from(elixir_table: ElixirTableIdent) -> Result<Self, Self::Error> {
+        if elixir_table.name.is_empty() {
+            return Err(ElixirError::new(
+                ElixirErrorKind::InvalidArgument,
+                "Invalid table identifier: table name is empty",
+            ));
+        }
+
+        Ok(TableIdent::new(
+            elixir_table.namespace.try_into()?,
+            elixir_table.name,
+        ))
     }
 }
 
@@ -38,215 +67,277 @@ impl From<TableIdent> for ElixirTableIdent {
     }
 }
 
-// REST Catalog Resource for wrapping iceberg-rust RestCatalog
-pub struct RestCatalogResource {
-    uri: String,
-    warehouse: Option<String>,
-    props: HashMap<String, String>,
-    runtime: Arc<Runtime>,
+// Catalog Resource shared by every catalog type
+//
+// Namespace and table operations run against an `iceberg::Catalog` trait
+// object, so the NIFs below work the same way for every backend. REST
+// catalogs build that catalog per token and add retries, vended credentials
+// and remote signing on top, see RestCatalogResource.
+pub struct CatalogResource {
+    backend: CatalogBackend,
+    retry: RetryPolicy,
+}
+
+enum CatalogBackend {
+    Rest(Box<RestCatalogResource>),
+    // Catalogs without per-call state, such as the memory and SQL catalogs
+    Shared(Arc<dyn Catalog>),
 }
 
-unsafe impl Send for RestCatalogResource {}
-unsafe impl Sync for RestCatalogResource {}
+unsafe impl Send for CatalogResource {}
+unsafe impl Sync for CatalogResource {}
+// See RestCatalogResource, shared catalogs keep their state in the catalog
+// or its database and update it as a whole.
+impl RefUnwindSafe for CatalogResource {}
 
 #[rustler::resource_impl]
-impl rustler::Resource for RestCatalogResource {}
+impl rustler::Resource for CatalogResource {}
 
-impl RestCatalogResource {
-    pub fn new(uri: String, warehouse: Option<String>, props: HashMap<String, String>) -> Self {
+impl From<RestCatalogResource> for CatalogResource {
+    fn from(catalog: RestCatalogResource) -> Self {
         Self {
-            uri,
-            warehouse,
-            props,
-            runtime: Arc::new(Runtime::new().unwrap()),
+            retry: *catalog.retry(),
+            backend: CatalogBackend::Rest(Box::new(catalog)),
         }
     }
+}
 
-    pub async fn get_catalog(&self) -> iceberg::Result<RestCatalog> {
-        let mut props = self.props.clone();
-
-        // Add required properties
-        props.insert("uri".to_string(), self.uri.clone());
-        if let Some(warehouse) = &self.warehouse {
-            props.insert("warehouse".to_string(), warehouse.clone());
+impl CatalogResource {
+    pub fn new(catalog: impl Catalog + 'static) -> Self {
+        Self {
+            backend: CatalogBackend::Shared(Arc::new(catalog)),
+            retry: RetryPolicy::default(),
         }
-
-        // Create catalog using the new builder API
-        RestCatalogBuilder::default()
-            .load("ex_iceberg", props)
-            .await
     }
-}
-
-#[derive(NifStruct)]
-#[module = "ExIceberg.Rest.CatalogConfig"]
-struct CatalogConfig {
-    uri: String,
-    warehouse: Option<String>,
-    token: Option<String>,
-    credential: Option<String>,
-    oauth2_server_uri: Option<String>,
-    scope: Option<String>,
-    audience: Option<String>,
-    resource: Option<String>,
-}
-
-#[derive(NifTaggedEnum)]
-enum TableResult {
-    Ok(ResourceArc<SmartTableResource>),
-    Error(String),
-}
 
-#[rustler::nif]
-pub fn rest_catalog_new(config: CatalogConfig) -> (Atom, ResourceArc<RestCatalogResource>) {
-    let mut props = HashMap::new();
-
-    if let Some(credential) = config.credential {
-        props.insert("credential".to_string(), credential);
+    /// Returns the catalog to run a request with. An `identity` is only
+    /// supported by REST catalogs, see `RestCatalogResource::catalog`.
+    pub async fn catalog(
+        &self,
+        identity: Option<&Identity>,
+    ) -> Result<Arc<dyn Catalog>, ElixirError> {
+        match (&self.backend, identity) {
+            (CatalogBackend::Rest(catalog), _) => Ok(catalog.catalog(identity).await?),
+            (CatalogBackend::Shared(catalog), None) => Ok(catalog.clone()),
+            (CatalogBackend::Shared(_), Some(_)) => Err(ElixirError::new(
+                ElixirErrorKind::NotSupported,
+                "Identity overrides are only supported by REST catalogs",
+            )),
+        }
     }
 
-    if let Some(oauth2_server_uri) = config.oauth2_server_uri {
-        props.insert("oauth2-server-uri".to_string(), oauth2_server_uri);
+    /// The REST catalog behind this resource, for REST-only features such as
+    /// tokens, vended credentials and remote signing.
+    pub fn rest(&self) -> Option<&RestCatalogResource> {
+        match &self.backend {
+            CatalogBackend::Rest(catalog) => Some(catalog),
+            CatalogBackend::Shared(_) => None,
+        }
     }
 
-    if let Some(scope) = config.scope {
-        props.insert("scope".to_string(), scope);
+    pub fn retry(&self) -> &RetryPolicy {
+        &self.retry
     }
 
-    if let Some(token) = config.token {
-        props.insert("token".to_string(), token);
+    pub async fn load_table(
+        &self,
+        table_ident: &TableIdent,
+        identity: Option<&Identity>,
+    ) -> Result<Table, ElixirError> {
+        let catalog = self.catalog(identity).await?;
+
+        self.retry
+            .run(true, "Failed to load table", || {
+                catalog.load_table(table_ident)
+            })
+            .await
     }
+}
 
-    if let Some(audience) = config.audience {
-        props.insert("audience".to_string(), audience);
+/// Builds a catalog of `catalog_type` from its properties, like pyiceberg's
+/// `load_catalog`.
+pub async fn load_catalog(
+    catalog_type: &str,
+    props: HashMap<String, String>,
+) -> Result<CatalogResource, ElixirError> {
+    match catalog_type {
+        "rest" => rest_catalog::load(props).await,
+        "memory" => memory_catalog::load(props).await,
+        "glue" => glue_catalog::load(props).await,
+        "hive" | "hms" => hms_catalog::load(props).await,
+        "sql" => sql_catalog::load(props).await,
+        _ => Err(ElixirError::new(
+            ElixirErrorKind::InvalidArgument,
+            format!("Unsupported catalog type: {}", catalog_type),
+        )),
     }
+}
 
-    if let Some(resource) = config.resource {
-        props.insert("resource".to_string(), resource);
-    }
+#[rustler::nif]
+pub fn catalog_load<'a>(
+    env: Env<'a>,
+    catalog_type: String,
+    properties: HashMap<String, String>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let catalog_resource = load_catalog(&catalog_type, properties).await?;
+        Ok(ResourceArc::new(catalog_resource))
+    })
+}
 
-    let catalog_resource = RestCatalogResource::new(config.uri, config.warehouse, props);
+#[rustler::nif]
+pub fn catalog_list_namespaces<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        let namespaces = catalog_resource
+            .retry()
+            .run(true, "Failed to list namespaces", || {
+                catalog.list_namespaces(None)
+            })
+            .await?;
+
+        Ok(namespaces
+            .into_iter()
+            .map(ElixirNamespaceIdent::from)
+            .collect::<Vec<_>>())
+    })
+}
 
-    (atoms::ok(), ResourceArc::new(catalog_resource))
+#[rustler::nif]
+pub fn catalog_create_namespace<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
+    namespace: ElixirNamespaceIdent,
+    properties: HashMap<String, String>,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let namespace_ident: NamespaceIdent = namespace.try_into()?;
+
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        let namespace_obj = catalog_resource
+            .retry()
+            .run(false, "Failed to create namespace", || {
+                catalog.create_namespace(&namespace_ident, properties.clone())
+            })
+            .await?;
+
+        Ok(ElixirNamespaceIdent::from(namespace_obj.name().clone()))
+    })
 }
 
 #[rustler::nif]
-pub fn rest_catalog_list_namespaces(
-    catalog_resource: ResourceArc<RestCatalogResource>,
-) -> (Atom, Vec<ElixirNamespaceIdent>) {
-    let runtime = catalog_resource.runtime.clone();
-
-    let result = runtime.block_on(async {
-        let catalog = catalog_resource.get_catalog().await?;
-        catalog.list_namespaces(None).await
-    });
-
-    match result {
-        Ok(namespaces) => {
-            let elixir_namespaces: Vec<ElixirNamespaceIdent> =
-                namespaces.into_iter().map(|ns| ns.into()).collect();
-            (atoms::ok(), elixir_namespaces)
-        }
-        Err(e) => {
-            let error_msg = format!("Failed to list namespaces: {}", e);
-            (
-                atoms::error(),
-                vec![ElixirNamespaceIdent {
-                    parts: vec![error_msg],
-                }],
-            )
-        }
-    }
+pub fn catalog_drop_namespace<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
+    namespace: ElixirNamespaceIdent,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let namespace_ident: NamespaceIdent = namespace.clone().try_into()?;
+
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        catalog_resource
+            .retry()
+            .run(false, "Failed to drop namespace", || {
+                catalog.drop_namespace(&namespace_ident)
+            })
+            .await?;
+
+        Ok(namespace)
+    })
 }
 
 #[rustler::nif]
-pub fn rest_catalog_create_namespace(
-    catalog_resource: ResourceArc<RestCatalogResource>,
+pub fn catalog_drop_namespace_cascade<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
     namespace: ElixirNamespaceIdent,
-    properties: HashMap<String, String>,
-) -> (Atom, ElixirNamespaceIdent) {
-    let runtime = catalog_resource.runtime.clone();
-    let namespace_ident: NamespaceIdent = namespace.into();
-
-    let result = runtime.block_on(async {
-        let catalog = catalog_resource.get_catalog().await?;
-        catalog.create_namespace(&namespace_ident, properties).await
-    });
-
-    match result {
-        Ok(namespace_obj) => {
-            let elixir_namespace: ElixirNamespaceIdent = namespace_obj.name().clone().into();
-            (atoms::ok(), elixir_namespace)
-        }
-        Err(e) => {
-            let error_namespace = ElixirNamespaceIdent {
-                parts: vec![format!("Failed to create namespace: {}", e)],
-            };
-            (atoms::error(), error_namespace)
-        }
-    }
+    purge: bool,
+    dry_run: bool,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let namespace_ident: NamespaceIdent = namespace.try_into()?;
+
+        cascade::drop_namespace(catalog_resource, &namespace_ident, purge, dry_run, identity).await
+    })
 }
 
 #[rustler::nif]
-pub fn rest_catalog_table_exists(
-    catalog_resource: ResourceArc<RestCatalogResource>,
+pub fn catalog_table_exists<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
     table_ident: ElixirTableIdent,
-) -> (Atom, bool) {
-    let runtime = catalog_resource.runtime.clone();
-    let table_ident: TableIdent = table_ident.into();
-
-    let result = runtime.block_on(async {
-        let catalog = catalog_resource.get_catalog().await?;
-        catalog.table_exists(&table_ident).await
-    });
-
-    match result {
-        Ok(exists) => (atoms::ok(), exists),
-        Err(_) => (atoms::error(), false),
-    }
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let table_ident: TableIdent = table_ident.try_into()?;
+
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        catalog_resource
+            .retry()
+            .run(true, "Failed to check table existence", || {
+                catalog.table_exists(&table_ident)
+            })
+            .await
+    })
 }
 
 #[rustler::nif]
-pub fn rest_catalog_drop_table(
-    catalog_resource: ResourceArc<RestCatalogResource>,
+pub fn catalog_drop_table<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
     table_ident: ElixirTableIdent,
-) -> (Atom, ElixirTableIdent) {
-    let runtime = catalog_resource.runtime.clone();
-    let table_ident_rust: TableIdent = table_ident.clone().into();
-
-    let result = runtime.block_on(async {
-        let catalog = catalog_resource.get_catalog().await?;
-        catalog.drop_table(&table_ident_rust).await
-    });
-
-    match result {
-        Ok(()) => (atoms::ok(), table_ident),
-        Err(e) => {
-            let error_table = ElixirTableIdent {
-                namespace: ElixirNamespaceIdent {
-                    parts: vec![format!("Failed to drop table: {}", e)],
-                },
-                name: "error".to_string(),
-            };
-            (atoms::error(), error_table)
-        }
-    }
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let table_ident_rust: TableIdent = table_ident.clone().try_into()?;
+
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        catalog_resource
+            .retry()
+            .run(false, "Failed to drop table", || {
+                catalog.drop_table(&table_ident_rust)
+            })
+            .await?;
+
+        Ok(table_ident)
+    })
 }
 
 #[rustler::nif]
-pub fn rest_catalog_create_table(
-    catalog_resource: ResourceArc<RestCatalogResource>,
+pub fn catalog_purge_table<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
     table_ident: ElixirTableIdent,
-    fields: Vec<IcebergField>,
-    properties: HashMap<String, String>,
-) -> TableResult {
-    let runtime = catalog_resource.runtime.clone();
-
-    // Extract namespace and table name
-    let namespace = table_ident.namespace.parts.join(".");
-    let table_name = table_ident.name.clone();
-    let namespace_ident: NamespaceIdent = table_ident.namespace.clone().into();
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let table_ident: TableIdent = table_ident.try_into()?;
+
+        purge::purge_table(&catalog_resource, &table_ident, identity.as_ref()).await
+    })
+}
 
+fn build_schema(fields: Vec<IcebergField>) -> Result<Schema, ElixirError> {
     // Convert IcebergField to NestedField
     let nested_fields: Vec<Arc<NestedField>> = fields
         .into_iter()
@@ -357,118 +448,266 @@ pub fn rest_catalog_create_table(
         })
         .collect();
 
-    // Create the schema
-    let table_schema = Schema::builder()
+    Schema::builder()
         .with_fields(nested_fields)
         .build()
-        .unwrap();
+        .map_err(|e| ElixirError::from_iceberg("Invalid table schema", e))
+}
 
-    // Create table creation spec
-    let table_creation = TableCreation::builder()
-        .name(table_name.clone())
-        .schema(table_schema)
-        .properties(properties)
-        .build();
-
-    let result = runtime.block_on(async {
-        let catalog = catalog_resource.get_catalog().await?;
-        catalog.create_table(&namespace_ident, table_creation).await
-    });
-
-    match result {
-        Ok(_table) => {
-            // Table created successfully, return SmartTableResource like load_table
-            let table_resource = SmartTableResource::new(
-                catalog_resource.uri.clone(),
-                catalog_resource.warehouse.clone(),
-                catalog_resource.props.clone(),
-                namespace,
-                table_name,
-                catalog_resource.runtime.clone(),
-            );
-            TableResult::Ok(ResourceArc::new(table_resource))
-        }
-        Err(e) => TableResult::Error(format!("Failed to create table: {}", e)),
-    }
+#[rustler::nif]
+pub fn catalog_create_table<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
+    table_ident: ElixirTableIdent,
+    fields: Vec<IcebergField>,
+    properties: HashMap<String, String>,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let table_ident_rust: TableIdent = table_ident.try_into()?;
+
+        let table_schema = build_schema(fields)?;
+
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        let table = catalog_resource
+            .retry()
+            .run(false, "Failed to create table", || {
+                // Create table creation spec, rebuilt for every attempt
+                let table_creation = TableCreation::builder()
+                    .name(table_ident_rust.name().to_string())
+                    .schema(table_schema.clone())
+                    .properties(properties.clone())
+                    .build();
+
+                catalog.create_table(table_ident_rust.namespace(), table_creation)
+            })
+            .await?;
+
+        // Table created successfully, return SmartTableResource like load_table
+        let table_resource = SmartTableResource::new(catalog_resource.clone(), table);
+        Ok(ResourceArc::new(table_resource))
+    })
 }
 
 #[rustler::nif]
-pub fn rest_catalog_load_table(
-    catalog_resource: ResourceArc<RestCatalogResource>,
+pub fn catalog_load_table<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
     table_ident: ElixirTableIdent,
-) -> TableResult {
-    let runtime = catalog_resource.runtime.clone();
-
-    let namespace = table_ident.namespace.parts.join(".");
-    let table_name = table_ident.name.clone();
-    let table_ident_rust: TableIdent = table_ident.into();
-
-    // First check if table exists by trying to load it
-    let load_result = runtime.block_on(async {
-        let catalog = catalog_resource.get_catalog().await?;
-        catalog.load_table(&table_ident_rust).await
-    });
-
-    match load_result {
-        Ok(_table) => {
-            // Table exists, create SmartTableResource
-            let table_resource = SmartTableResource::new(
-                catalog_resource.uri.clone(),
-                catalog_resource.warehouse.clone(),
-                catalog_resource.props.clone(),
-                namespace,
-                table_name,
-                catalog_resource.runtime.clone(),
-            );
-            TableResult::Ok(ResourceArc::new(table_resource))
-        }
-        Err(e) => TableResult::Error(format!("Failed to load table: {}", e)),
-    }
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let table_ident_rust: TableIdent = table_ident.try_into()?;
+
+        let table = catalog_resource
+            .load_table(&table_ident_rust, identity.as_ref())
+            .await?;
+
+        let table_resource = SmartTableResource::new(catalog_resource.clone(), table);
+        Ok(ResourceArc::new(table_resource))
+    })
 }
 
 #[rustler::nif]
-pub fn rest_catalog_rename_table(
-    catalog_resource: ResourceArc<RestCatalogResource>,
+pub fn catalog_register_table<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
+    table_ident: ElixirTableIdent,
+    metadata_location: String,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let table_ident_rust: TableIdent = table_ident.try_into()?;
+
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        let table = catalog_resource
+            .retry()
+            .run(false, "Failed to register table", || {
+                catalog.register_table(&table_ident_rust, metadata_location.clone())
+            })
+            .await?;
+
+        let table_resource = SmartTableResource::new(catalog_resource.clone(), table);
+        Ok(ResourceArc::new(table_resource))
+    })
+}
+
+#[rustler::nif]
+pub fn catalog_rename_table<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
     src_table_ident: ElixirTableIdent,
     dest_table_ident: ElixirTableIdent,
-) -> (Atom, HashMap<String, String>) {
-    let runtime = catalog_resource.runtime.clone();
-    let src_table_ident_rust: TableIdent = src_table_ident.clone().into();
-    let dest_table_ident_rust: TableIdent = dest_table_ident.clone().into();
-
-    let result = runtime.block_on(async {
-        let catalog = catalog_resource.get_catalog().await?;
-        catalog
-            .rename_table(&src_table_ident_rust, &dest_table_ident_rust)
-            .await
-    });
-
-    match result {
-        Ok(()) => {
-            let mut response = HashMap::new();
-            let src_full_name = format!(
-                "{}.{}",
-                src_table_ident.namespace.parts.join("."),
-                src_table_ident.name
-            );
-            let dest_full_name = format!(
-                "{}.{}",
-                dest_table_ident.namespace.parts.join("."),
-                dest_table_ident.name
-            );
-            response.insert(
-                "renamed".to_string(),
-                format!("{} -> {}", src_full_name, dest_full_name),
-            );
-            (atoms::ok(), response)
-        }
-        Err(e) => {
-            let mut error_response = HashMap::new();
-            error_response.insert(
-                "error".to_string(),
-                format!("Failed to rename table: {}", e),
-            );
-            (atoms::error(), error_response)
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let src_table_ident_rust: TableIdent = src_table_ident.clone().try_into()?;
+        let dest_table_ident_rust: TableIdent = dest_table_ident.clone().try_into()?;
+
+        let catalog = catalog_resource.catalog(identity.as_ref()).await?;
+
+        catalog_resource
+            .retry()
+            .run(false, "Failed to rename table", || {
+                catalog.rename_table(&src_table_ident_rust, &dest_table_ident_rust)
+            })
+            .await?;
+
+        Ok(rename_response(&src_table_ident, &dest_table_ident))
+    })
+}
+
+fn rename_response(
+    src_table_ident: &ElixirTableIdent,
+    dest_table_ident: &ElixirTableIdent,
+) -> HashMap<String, String> {
+    let mut response = HashMap::new();
+    let src_full_name = format!(
+        "{}.{}",
+        src_table_ident.namespace.parts.join("."),
+        src_table_ident.name
+    );
+    let dest_full_name = format!(
+        "{}.{}",
+        dest_table_ident.namespace.parts.join("."),
+        dest_table_ident.name
+    );
+    response.insert(
+        "renamed".to_string(),
+        format!("{} -> {}", src_full_name, dest_full_name),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::test_support;
+
+    fn field(name: &str, field_type: IcebergFieldType) -> IcebergField {
+        IcebergField {
+            name: name.to_string(),
+            field_type,
+            required: false,
         }
     }
+
+    #[test]
+    fn empty_namespace_is_invalid_argument() {
+        let result = NamespaceIdent::try_from(ElixirNamespaceIdent { parts: vec![] });
+
+        let error = result.unwrap_err();
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+        assert!(error.message.starts_with("Invalid namespace"));
+    }
+
+    #[test]
+    fn empty_table_name_is_invalid_argument() {
+        let result = TableIdent::try_from(ElixirTableIdent {
+            namespace: ElixirNamespaceIdent {
+                parts: vec!["ns".to_string()],
+            },
+            name: String::new(),
+        });
+
+        assert_eq!(result.unwrap_err().kind, ElixirErrorKind::InvalidArgument);
+    }
+
+    #[test]
+    fn table_ident_with_empty_namespace_is_invalid_argument() {
+        let result = TableIdent::try_from(ElixirTableIdent {
+            namespace: ElixirNamespaceIdent { parts: vec![] },
+            name: "my_table".to_string(),
+        });
+
+        assert_eq!(result.unwrap_err().kind, ElixirErrorKind::InvalidArgument);
+    }
+
+    #[test]
+    fn valid_table_ident_converts() {
+        let table_ident = TableIdent::try_from(ElixirTableIdent {
+            namespace: ElixirNamespaceIdent {
+                parts: vec!["level1".to_string(), "level2".to_string()],
+            },
+            name: "my_table".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(
+            table_ident.namespace().clone().inner(),
+            ["level1", "level2"]
+        );
+        assert_eq!(table_ident.name(), "my_table");
+    }
+
+    #[test]
+    fn table_ident_round_trip_keeps_dotted_namespace_levels() {
+        let elixir_ident = ElixirTableIdent {
+            namespace: ElixirNamespaceIdent {
+                parts: vec!["a.b".to_string(), "c".to_string()],
+            },
+            name: "my.table".to_string(),
+        };
+
+        let table_ident = TableIdent::try_from(elixir_ident).unwrap();
+        assert_eq!(table_ident.namespace().clone().inner(), ["a.b", "c"]);
+
+        let round_trip: ElixirTableIdent = table_ident.into();
+        assert_eq!(round_trip.namespace.parts, ["a.b", "c"]);
+        assert_eq!(round_trip.name, "my.table");
+    }
+
+    #[test]
+    fn build_schema_rejects_duplicate_field_names() {
+        let result = build_schema(vec![
+            field("id", IcebergFieldType::Long),
+            field("id", IcebergFieldType::String),
+        ]);
+
+        let error = result.unwrap_err();
+        assert!(error.message.starts_with("Invalid table schema"));
+    }
+
+    #[test]
+    fn build_schema_accepts_valid_fields() {
+        let schema = build_schema(vec![
+            field("id", IcebergFieldType::Long),
+            field("name", IcebergFieldType::String),
+        ])
+        .unwrap();
+
+        assert_eq!(schema.as_struct().fields().len(), 2);
+    }
+
+    #[test]
+    fn load_catalog_rejects_unknown_type() {
+        let error = runtime()
+            .unwrap()
+            .block_on(load_catalog("nessie", HashMap::new()))
+            .err()
+            .unwrap();
+
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+        assert_eq!(error.message, "Unsupported catalog type: nessie");
+    }
+
+    #[test]
+    fn shared_catalog_rejects_identity() {
+        let catalog = test_support::memory_catalog();
+        let identity = Identity::Token("tenant-token".to_string());
+
+        let error = runtime()
+            .unwrap()
+            .block_on(catalog.catalog(Some(&identity)))
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ElixirErrorKind::NotSupported);
+    }
 }
@@ -0,0 +1,212 @@
+use iceberg::table::Table;
+use rustler::NifMap;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::ElixirError;
+use crate::file_io;
+
+// Credentials are refreshed this long before they expire
+pub const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+// FileIO properties of the storage backends, vended credentials only set these
+const STORAGE_PREFIXES: &[&str] = &["s3.", "gcs.", "adls.", "client."];
+
+// Properties holding the expiry of vended credentials, in milliseconds since
+// the epoch. ADLS suffixes the key with the storage account name.
+const EXPIRY_PREFIXES: &[&str] = &[
+    "s3.session-token-expires-at-ms",
+    "gcs.oauth2.token-expires-at",
+    "adls.sas-token-expires-at-ms",
+];
+
+// Entry of `storage-credentials`, applying to the locations under `prefix`
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageCredential {
+    pub prefix: String,
+    pub config: HashMap<String, String>,
+}
+
+// Storage properties the catalog vended for a table, as used by its FileIO
+#[derive(Debug, Clone, Default)]
+pub struct VendedCredentials {
+    pub config: HashMap<String, String>,
+    pub expires_at: Option<SystemTime>,
+}
+
+// Vended credentials returned to Elixir
+#[derive(Debug, NifMap)]
+pub struct VendedCredentialsInfo {
+    pub config: HashMap<String, String>,
+    // Unix time in seconds, nil when the expiry is unknown
+    pub expires_at: Option<u64>,
+}
+
+impl VendedCredentials {
+    /// Reads the vended properties from the table's FileIO. RestCatalog merges
+    /// the `config` of the load-table response into it, under the catalog's
+    /// own properties, so the ones given in `catalog_props` are left out.
+    pub fn from_table(table: &Table, catalog_props: &HashMap<String, String>) -> Self {
+        let (_, props, _) = table.file_io().clone().into_builder().into_parts();
+
+        let config: HashMap<String, String> = props
+            .into_iter()
+            .filter(|(key, value)| {
+                is_storage_property(key) && catalog_props.get(key) != Some(value)
+            })
+            .collect();
+
+        Self {
+            expires_at: expiry(&config),
+            config,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.config.is_empty()
+    }
+
+    pub fn is_expiring(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= SystemTime::now() + REFRESH_MARGIN)
+    }
+
+    pub fn info(&self) -> VendedCredentialsInfo {
+        VendedCredentialsInfo {
+            config: self.config.clone(),
+            expires_at: self
+                .expires_at
+                .and_then(|expires_at| expires_at.duration_since(UNIX_EPOCH).ok())
+                .map(|expires_at| expires_at.as_secs()),
+        }
+    }
+}
+
+/// Picks the credential with the longest prefix matching `location`.
+pub fn select(credentials: Vec<StorageCredential>, location: &str) -> Option<StorageCredential> {
+    credentials
+        .into_iter()
+        .filter(|credential| location.starts_with(&credential.prefix))
+        .max_by_key(|credential| credential.prefix.len())
+}
+
+/// Returns the table with its FileIO rebuilt with `config` applied on top of
+/// its current properties. The storage properties in `catalog_props` take
+/// precedence, as they do when RestCatalog loads the table, the others such
+/// as `uri` or `token` are not FileIO properties and are left out.
+pub fn apply(
+    table: &Table,
+    config: HashMap<String, String>,
+    catalog_props: &HashMap<String, String>,
+) -> Result<Table, ElixirError> {
+    let (_, mut props, _) = table.file_io().clone().into_builder().into_parts();
+    props.extend(config);
+    props.extend(
+        catalog_props
+            .iter()
+            .filter(|(key, _)| is_storage_property(key))
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+
+    file_io::with_props(table, props)
+        .map_err(|e| ElixirError::from_iceberg("Failed to apply vended credentials", e))
+}
+
+fn is_storage_property(key: &str) -> bool {
+    STORAGE_PREFIXES
+        .iter()
+        .any(|prefix| key.starts_with(prefix))
+}
+
+// The earliest expiry when several credentials are vended
+fn expiry(config: &HashMap<String, String>) -> Option<SystemTime> {
+    config
+        .iter()
+        .filter(|(key, _)| EXPIRY_PREFIXES.iter().any(|prefix| key.starts_with(prefix)))
+        .filter_map(|(_, value)| value.parse().ok())
+        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_catalog;
+    use crate::runtime::runtime;
+    use crate::test_support;
+    use iceberg::{NamespaceIdent, TableIdent};
+
+    fn credential(prefix: &str) -> StorageCredential {
+        StorageCredential {
+            prefix: prefix.to_string(),
+            config: HashMap::from([("s3.access-key-id".to_string(), prefix.to_string())]),
+        }
+    }
+
+    #[test]
+    fn selects_longest_matching_prefix() {
+        let credentials = vec![
+            credential("s3://bucket/"),
+            credential("s3://bucket/warehouse/"),
+            credential("s3://other/"),
+        ];
+
+        let selected = select(credentials, "s3://bucket/warehouse/db/orders").unwrap();
+
+        assert_eq!(selected.prefix, "s3://bucket/warehouse/");
+        assert!(select(vec![credential("s3://other/")], "s3://bucket/t").is_none());
+    }
+
+    #[test]
+    fn reads_earliest_expiry() {
+        let config = HashMap::from([
+            (
+                "s3.session-token-expires-at-ms".to_string(),
+                "1700000060000".to_string(),
+            ),
+            (
+                "adls.sas-token-expires-at-ms.account".to_string(),
+                "1700000000000".to_string(),
+            ),
+            ("s3.access-key-id".to_string(), "key".to_string()),
+        ]);
+
+        let credentials = VendedCredentials {
+            expires_at: expiry(&config),
+            config,
+        };
+
+        assert_eq!(credentials.info().expires_at, Some(1_700_000_000));
+        assert!(credentials.is_expiring());
+    }
+
+    #[test]
+    fn keeps_user_properties_on_refresh() {
+        let file_io_props = HashMap::from([(
+            "s3.endpoint".to_string(),
+            "http://localhost:9000".to_string(),
+        )]);
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+        let table = runtime().unwrap().block_on(async {
+            let catalog_resource = memory_catalog::load(file_io_props.clone()).await.unwrap();
+            test_support::create_table(&catalog_resource, &table_ident).await
+        });
+
+        // Catalog properties that are not FileIO properties stay out of it
+        let mut catalog_props = file_io_props;
+        catalog_props.insert("token".to_string(), "catalog-token".to_string());
+
+        let config = HashMap::from([
+            ("s3.endpoint".to_string(), "http://vended:9000".to_string()),
+            ("s3.access-key-id".to_string(), "vended".to_string()),
+        ]);
+        let table = apply(&table, config, &catalog_props).unwrap();
+
+        let (_, props, _) = table.file_io().clone().into_builder().into_parts();
+        assert_eq!(props["s3.endpoint"], "http://localhost:9000");
+        assert_eq!(props["s3.access-key-id"], "vended");
+        assert!(!props.contains_key("token"));
+    }
+}
@@ -0,0 +1,317 @@
+use rustler::{NifException, NifUnitEnum};
+use std::error::Error as StdError;
+
+use iceberg::ErrorKind;
+
+// Encoded as the snake_case atom in `ExIceberg.Error.kind`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, NifUnitEnum)]
+pub enum ElixirErrorKind {
+    NoSuchTable,
+    NoSuchNamespace,
+    AlreadyExists,
+    NamespaceNotEmpty,
+    CommitFailed,
+    CommitStateUnknown,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    InvalidArgument,
+    NotSupported,
+    PreconditionFailed,
+    ServiceUnavailable,
+    // Only raised on the Elixir side, when a call exceeds its `:timeout`
+    Timeout,
+    DataInvalid,
+    Unexpected,
+}
+
+// Error struct returned to Elixir as `%ExIceberg.Error{}`
+#[derive(Debug, NifException)]
+#[module = "ExIceberg.Error"]
+pub struct ElixirError {
+    pub kind: ElixirErrorKind,
+    pub message: String,
+    pub http_status: Option<u16>,
+    pub source: Option<String>,
+}
+
+impl ElixirError {
+    pub fn new(kind: ElixirErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            http_status: None,
+            source: None,
+        }
+    }
+
+    /// Builds an error from an iceberg error, prefixing the message with the
+    /// operation that failed (e.g. "Failed to load table").
+    pub fn from_iceberg(operation: &str, err: iceberg::Error) -> Self {
+        let known = known_message(err.message());
+        let http_status = known
+            .and_then(|(_, status)| status)
+            .or_else(|| unexpected_status(&err));
+
+        let kind = kind_from_error_kind(err.kind())
+            .or(known.map(|(kind, _)| kind))
+            .or_else(|| kind_from_formatted_message(err.message()))
+            .or_else(|| http_status.and_then(kind_from_status))
+            .unwrap_or_else(|| match err.kind() {
+                ErrorKind::DataInvalid => ElixirErrorKind::DataInvalid,
+                _ => ElixirErrorKind::Unexpected,
+            });
+
+        Self {
+            kind,
+            message: format!("{}: {}", operation, err),
+            http_status,
+            source: err.source().map(|source| source.to_string()),
+        }
+    }
+
+    /// Builds the error of a REST catalog response, from the exception type
+    /// of its error model when it has one.
+    pub fn from_response(status: u16, rest_type: Option<&str>, message: impl Into<String>) -> Self {
+        let kind = rest_type
+            .and_then(kind_from_rest_type)
+            .or_else(|| kind_from_status(status))
+            .unwrap_or(ElixirErrorKind::Unexpected);
+
+        Self {
+            http_status: Some(status),
+            ..Self::new(kind, message)
+        }
+    }
+}
+
+// Failures `RetryPolicy` reports, prefixed with the operation that failed
+pub trait OperationError {
+    fn into_elixir_error(self, operation: &str) -> ElixirError;
+}
+
+impl OperationError for iceberg::Error {
+    fn into_elixir_error(self, operation: &str) -> ElixirError {
+        ElixirError::from_iceberg(operation, self)
+    }
+}
+
+impl OperationError for ElixirError {
+    fn into_elixir_error(self, operation: &str) -> ElixirError {
+        ElixirError {
+            message: format!("{}: {}", operation, self.message),
+            ..self
+        }
+    }
+}
+
+fn kind_from_error_kind(kind: ErrorKind) -> Option<ElixirErrorKind> {
+    match kind {
+        ErrorKind::TableNotFound => Some(ElixirErrorKind::NoSuchTable),
+        ErrorKind::NamespaceNotFound => Some(ElixirErrorKind::NoSuchNamespace),
+        ErrorKind::TableAlreadyExists | ErrorKind::NamespaceAlreadyExists => {
+            Some(ElixirErrorKind::AlreadyExists)
+        }
+        ErrorKind::CatalogCommitConflicts => Some(ElixirErrorKind::CommitFailed),
+        ErrorKind::FeatureUnsupported => Some(ElixirErrorKind::NotSupported),
+        ErrorKind::PreconditionFailed => Some(ElixirErrorKind::PreconditionFailed),
+        _ => None,
+    }
+}
+
+// Exception types from the REST catalog error model
+fn kind_from_rest_type(rest_type: &str) -> Option<ElixirErrorKind> {
+    match rest_type {
+        "NoSuchTableException" | "NoSuchViewException" => Some(ElixirErrorKind::NoSuchTable),
+        "NoSuchNamespaceException" => Some(ElixirErrorKind::NoSuchNamespace),
+        "AlreadyExistsException" => Some(ElixirErrorKind::AlreadyExists),
+        "NamespaceNotEmptyException" => Some(ElixirErrorKind::NamespaceNotEmpty),
+        "CommitFailedException" => Some(ElixirErrorKind::CommitFailed),
+        "CommitStateUnknownException" => Some(ElixirErrorKind::CommitStateUnknown),
+        "NotAuthorizedException" => Some(ElixirErrorKind::Unauthorized),
+        "ForbiddenException" => Some(ElixirErrorKind::Forbidden),
+        "BadRequestException" | "IllegalArgumentException" => {
+            Some(ElixirErrorKind::InvalidArgument)
+        }
+        "UnsupportedOperationException" => Some(ElixirErrorKind::NotSupported),
+        "ServiceUnavailableException" => Some(ElixirErrorKind::ServiceUnavailable),
+        _ => None,
+    }
+}
+
+// RestCatalog raises `Unexpected` with a fixed message for each status it
+// handles, the message is all that tells them apart. Pinned by the
+// `rest_catalog` tests against a stub server.
+fn known_message(message: &str) -> Option<(ElixirErrorKind, Option<u16>)> {
+    let known = match message {
+        "Tried to create a namespace that already exists"
+        | "Tried to rename a table to a name that already exists" => {
+            (ElixirErrorKind::AlreadyExists, Some(409))
+        }
+        "The table already exists" => (ElixirErrorKind::AlreadyExists, None),
+        "Tried to get a namespace that does not exist"
+        | "Tried to drop a namespace that does not exist"
+        | "Tried to list tables of a namespace that does not exist" => {
+            (ElixirErrorKind::NoSuchNamespace, Some(404))
+        }
+        "The parent parameter of the namespace provided does not exist"
+        | "Tried to create a table under a namespace that does not exist" => {
+            (ElixirErrorKind::NoSuchNamespace, None)
+        }
+        "Tried to load a table that does not exist"
+        | "Tried to drop a table that does not exist"
+        | "Tried to rename a table that does not exist (is the namespace correct?)" => {
+            (ElixirErrorKind::NoSuchTable, Some(404))
+        }
+        "An unknown server-side problem occurred; the commit state is unknown." => {
+            (ElixirErrorKind::CommitStateUnknown, Some(500))
+        }
+        "A gateway or proxy received an invalid response from the upstream server; the commit state is unknown." => {
+            (ElixirErrorKind::CommitStateUnknown, Some(502))
+        }
+        "A server-side gateway timeout occurred; the commit state is unknown." => {
+            (ElixirErrorKind::CommitStateUnknown, Some(504))
+        }
+        _ => return None,
+    };
+
+    Some(known)
+}
+
+// The SQL and Glue catalogs raise `Unexpected` or `DataInvalid` for these,
+// formatted around the namespace or table identifier. Pinned by the
+// `sql_catalog` tests.
+fn kind_from_formatted_message(message: &str) -> Option<ElixirErrorKind> {
+    if let Some(namespace) = message.strip_prefix("Namespace NamespaceIdent(") {
+        if namespace.ends_with(") already exists") {
+            return Some(ElixirErrorKind::AlreadyExists);
+        }
+        if namespace.contains(") is not empty. ") && namespace.ends_with(" tables exist.") {
+            return Some(ElixirErrorKind::NamespaceNotEmpty);
+        }
+    }
+
+    if let Some(database) = message.strip_prefix("Database with name: ") {
+        if database.ends_with(" does not exist") {
+            return Some(ElixirErrorKind::NoSuchNamespace);
+        }
+        if database.ends_with(" is not empty") {
+            return Some(ElixirErrorKind::NamespaceNotEmpty);
+        }
+    }
+
+    if message.starts_with("No such namespace: NamespaceIdent(") {
+        Some(ElixirErrorKind::NoSuchNamespace)
+    } else if message.starts_with("No such table: TableIdent {") {
+        Some(ElixirErrorKind::NoSuchTable)
+    } else if message.starts_with("Table TableIdent {") && message.ends_with(" already exists.") {
+        Some(ElixirErrorKind::AlreadyExists)
+    } else {
+        None
+    }
+}
+
+fn kind_from_status(status: u16) -> Option<ElixirErrorKind> {
+    match status {
+        400 => Some(ElixirErrorKind::InvalidArgument),
+        401 | 419 => Some(ElixirErrorKind::Unauthorized),
+        403 => Some(ElixirErrorKind::Forbidden),
+        404 => Some(ElixirErrorKind::NotFound),
+        406 => Some(ElixirErrorKind::NotSupported),
+        409 => Some(ElixirErrorKind::AlreadyExists),
+        503 => Some(ElixirErrorKind::ServiceUnavailable),
+        _ => None,
+    }
+}
+
+// Status of a response RestCatalog did not expect. iceberg::Error has no
+// accessor for its context, and the status is only recorded there, so it is
+// read back from the rendered error for these two messages only.
+fn unexpected_status(err: &iceberg::Error) -> Option<u16> {
+    let key = match err.message() {
+        "Received response with unexpected status code" => "status: ",
+        "Received unexpected response" => "code: ",
+        _ => return None,
+    };
+
+    // The status is the first entry of the context in both
+    let details = err.to_string();
+    let value = details.split_once("context: { ")?.1.strip_prefix(key)?;
+
+    value
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .and_then(|code| code.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_rest_error_model() {
+        let error =
+            ElixirError::from_response(404, Some("NoSuchTableException"), "Table does not exist")
+                .into_elixir_error("Failed to load credentials");
+
+        assert_eq!(error.kind, ElixirErrorKind::NoSuchTable);
+        assert_eq!(error.http_status, Some(404));
+        assert_eq!(
+            error.message,
+            "Failed to load credentials: Table does not exist"
+        );
+
+        let error = ElixirError::from_response(409, Some("CustomException"), "Conflict");
+        assert_eq!(error.kind, ElixirErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn maps_unexpected_status() {
+        let err = iceberg::Error::new(
+            ErrorKind::Unexpected,
+            "Received response with unexpected status code",
+        )
+        .with_context("status", "401 Unauthorized")
+        .with_context("headers", "{}");
+
+        let error = ElixirError::from_iceberg("Failed to list namespaces", err);
+
+        assert_eq!(error.kind, ElixirErrorKind::Unauthorized);
+        assert_eq!(error.http_status, Some(401));
+    }
+
+    #[test]
+    fn maps_rest_catalog_messages() {
+        let err = iceberg::Error::new(ErrorKind::Unexpected, "The table already exists");
+
+        let error = ElixirError::from_iceberg("Failed to create table", err);
+
+        assert_eq!(error.kind, ElixirErrorKind::AlreadyExists);
+        assert_eq!(error.http_status, None);
+
+        let err = iceberg::Error::new(
+            ErrorKind::Unexpected,
+            "Namespace NamespaceIdent([\"db\"]) is not empty. 1 tables exist.",
+        );
+        let error = ElixirError::from_iceberg("Failed to drop namespace", err);
+
+        assert_eq!(error.kind, ElixirErrorKind::NamespaceNotEmpty);
+
+        // Only the messages the catalogs raise are recognised
+        let err = iceberg::Error::new(ErrorKind::Unexpected, "The file already exists");
+        let error = ElixirError::from_iceberg("Failed to create table", err);
+
+        assert_eq!(error.kind, ElixirErrorKind::Unexpected);
+    }
+
+    #[test]
+    fn keeps_source() {
+        let err = iceberg::Error::new(ErrorKind::Unexpected, "Failed to execute http request")
+            .with_source(std::io::Error::other("connection refused"));
+
+        let error = ElixirError::from_iceberg("Failed to list namespaces", err);
+
+        assert_eq!(error.kind, ElixirErrorKind::Unexpected);
+        assert_eq!(error.source.as_deref(), Some("connection refused"));
+    }
+}
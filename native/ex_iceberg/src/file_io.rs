@@ -0,0 +1,122 @@
+use iceberg::io::{FileIO, FileIOBuilder};
+use iceberg::table::Table;
+use std::collections::HashMap;
+
+use crate::error::{ElixirError, ElixirErrorKind};
+
+/// Builds the FileIO for `location` the way catalogs build it for their
+/// tables, `props` holding settings such as `s3.endpoint` or
+/// `s3.path-style-access`.
+///
+/// `s3://`, `file://` and `memory://` locations are supported. Note that each
+/// `memory://` FileIO has its own storage, shared only with its clones.
+pub fn build(location: &str, props: &HashMap<String, String>) -> Result<FileIO, ElixirError> {
+    FileIO::from_path(location)
+        .and_then(|builder| builder.with_props(props.clone()).build())
+        .map_err(|e| invalid_config(&e.to_string()))
+}
+
+/// Returns the table with its FileIO rebuilt with `props`, keeping its
+/// scheme and extensions.
+pub fn with_props(table: &Table, props: HashMap<String, String>) -> iceberg::Result<Table> {
+    let (scheme, _, extensions) = table.file_io().clone().into_builder().into_parts();
+    let file_io = FileIOBuilder::new(scheme)
+        .with_props(props)
+        .with_extensions(extensions)
+        .build()?;
+
+    let mut builder = Table::builder()
+        .identifier(table.identifier().clone())
+        .metadata(table.metadata_ref())
+        .file_io(file_io)
+        .readonly(table.readonly());
+
+    if let Some(metadata_location) = table.metadata_location() {
+        builder = builder.metadata_location(metadata_location);
+    }
+
+    builder.build()
+}
+
+/// Checks a warehouse given as a location, for catalogs that read and write
+/// their tables' files themselves. Logical warehouse names such as
+/// Lakekeeper's are left to the catalog.
+pub fn validate_warehouse(
+    warehouse: &str,
+    props: &HashMap<String, String>,
+) -> Result<(), ElixirError> {
+    if warehouse.contains("://") {
+        build(warehouse, props)?;
+    }
+
+    Ok(())
+}
+
+fn invalid_config(message: &str) -> ElixirError {
+    ElixirError::new(
+        ElixirErrorKind::InvalidArgument,
+        format!("Invalid FileIO configuration: {}", message),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_and_read(file_io: &FileIO, path: &str) -> Vec<u8> {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let output = file_io.new_output(path).unwrap();
+            output.write(b"iceberg".to_vec().into()).await.unwrap();
+            file_io
+                .new_input(path)
+                .unwrap()
+                .read()
+                .await
+                .unwrap()
+                .to_vec()
+        })
+    }
+
+    #[test]
+    fn reads_and_writes_local_files() {
+        let dir = std::env::temp_dir().join(format!("ex_iceberg_file_io_{}", std::process::id()));
+        let location = format!("file://{}", dir.display());
+
+        let file_io = build(&location, &HashMap::new()).unwrap();
+        let content = write_and_read(&file_io, &format!("{}/data/file.txt", location));
+
+        assert_eq!(content, b"iceberg");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reads_and_writes_memory_files() {
+        let file_io = build("memory://", &HashMap::new()).unwrap();
+        let content = write_and_read(&file_io, "memory://warehouse/file.txt");
+
+        assert_eq!(content, b"iceberg");
+    }
+
+    #[test]
+    fn builds_s3_file_io() {
+        let props = HashMap::from([
+            (
+                "s3.endpoint".to_string(),
+                "http://localhost:9000".to_string(),
+            ),
+            ("s3.region".to_string(), "local-01".to_string()),
+            ("s3.path-style-access".to_string(), "true".to_string()),
+        ]);
+
+        assert!(validate_warehouse("s3://examples/warehouse", &props).is_ok());
+        assert!(validate_warehouse("demo", &props).is_ok());
+    }
+
+    #[test]
+    fn rejects_unsupported_scheme() {
+        let error = validate_warehouse("hdfs://namenode/warehouse", &HashMap::new()).unwrap_err();
+
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+        assert!(error.message.starts_with("Invalid FileIO configuration"));
+    }
+}
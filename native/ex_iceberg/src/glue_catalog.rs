@@ -0,0 +1,137 @@
+use rustler::{Env, NifStruct, ResourceArc, Term};
+use std::collections::HashMap;
+
+use iceberg::CatalogBuilder;
+use iceberg_catalog_glue::{
+    GlueCatalogBuilder, AWS_ACCESS_KEY_ID, AWS_PROFILE_NAME, AWS_REGION_NAME,
+    AWS_SECRET_ACCESS_KEY, AWS_SESSION_TOKEN, GLUE_CATALOG_PROP_CATALOG_ID, GLUE_CATALOG_PROP_URI,
+    GLUE_CATALOG_PROP_WAREHOUSE,
+};
+
+use crate::catalog::CatalogResource;
+use crate::error::ElixirError;
+use crate::props;
+use crate::task::{reply_async, AsyncReply};
+
+// pyiceberg's Glue properties and the ones read by GlueCatalog
+const PROPERTY_ALIASES: [(&str, &str); 7] = [
+    ("glue.endpoint", GLUE_CATALOG_PROP_URI),
+    ("glue.id", GLUE_CATALOG_PROP_CATALOG_ID),
+    ("glue.region", AWS_REGION_NAME),
+    ("glue.access-key-id", AWS_ACCESS_KEY_ID),
+    ("glue.secret-access-key", AWS_SECRET_ACCESS_KEY),
+    ("glue.session-token", AWS_SESSION_TOKEN),
+    ("glue.profile-name", AWS_PROFILE_NAME),
+];
+
+#[derive(NifStruct)]
+#[module = "ExIceberg.Glue.CatalogConfig"]
+struct GlueCatalogConfig {
+    // Location of the tables, e.g. `s3://bucket/warehouse`
+    warehouse: String,
+    region: Option<String>,
+    // Glue endpoint override, e.g. `http://localhost:5000` for moto
+    endpoint: Option<String>,
+    // AWS account id of the Glue catalog, defaults to the caller's account
+    catalog_id: Option<String>,
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    session_token: Option<String>,
+    profile: Option<String>,
+    properties: HashMap<String, String>,
+    file_io: HashMap<String, String>,
+}
+
+/// Builds a Glue catalog from its properties, `warehouse` is required.
+///
+/// Region, endpoint and credentials are read from `region_name`, `uri`,
+/// `aws_access_key_id`, `aws_secret_access_key`, `aws_session_token` and
+/// `profile_name`, or pyiceberg's `glue.*` properties, and fall back to the
+/// AWS SDK defaults. They are passed on to the S3 FileIO unless the matching
+/// `s3.*` property is set.
+pub async fn load(mut props: HashMap<String, String>) -> Result<CatalogResource, ElixirError> {
+    for (alias, key) in PROPERTY_ALIASES {
+        if let Some(value) = props.remove(alias) {
+            props.entry(key.to_string()).or_insert(value);
+        }
+    }
+
+    props::required(&props, "Glue", GLUE_CATALOG_PROP_WAREHOUSE)?;
+
+    // Loading resolves the AWS config, which may read the shared config files
+    // or call the instance metadata service, but does not call Glue
+    let catalog = GlueCatalogBuilder::default()
+        .load("ex_iceberg", props)
+        .await
+        .map_err(|e| ElixirError::from_iceberg("Failed to create catalog", e))?;
+
+    Ok(CatalogResource::new(catalog))
+}
+
+#[rustler::nif]
+pub fn glue_catalog_new<'a>(
+    env: Env<'a>,
+    config: GlueCatalogConfig,
+    reference: Term<'a>,
+) -> AsyncReply {
+    let mut props = config.properties;
+    props.extend(config.file_io);
+    props.insert(GLUE_CATALOG_PROP_WAREHOUSE.to_string(), config.warehouse);
+
+    let options = [
+        (AWS_REGION_NAME, config.region),
+        (GLUE_CATALOG_PROP_URI, config.endpoint),
+        (GLUE_CATALOG_PROP_CATALOG_ID, config.catalog_id),
+        (AWS_ACCESS_KEY_ID, config.access_key_id),
+        (AWS_SECRET_ACCESS_KEY, config.secret_access_key),
+        (AWS_SESSION_TOKEN, config.session_token),
+        (AWS_PROFILE_NAME, config.profile),
+    ];
+    for (key, value) in options {
+        if let Some(value) = value {
+            props.insert(key.to_string(), value);
+        }
+    }
+
+    reply_async(env, reference, async move {
+        Ok(ResourceArc::new(load(props).await?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ElixirErrorKind;
+    use crate::runtime::runtime;
+    use crate::test_support::props;
+
+    #[test]
+    fn loads_from_pyiceberg_properties() {
+        let catalog = runtime().unwrap().block_on(load(props(&[
+            ("warehouse", "s3://warehouse/glue"),
+            ("glue.endpoint", "http://localhost:5000"),
+            ("glue.region", "us-east-1"),
+            ("glue.access-key-id", "testing"),
+            ("glue.secret-access-key", "testing"),
+        ])));
+
+        assert!(catalog.is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let runtime = runtime().unwrap();
+
+        let error = runtime
+            .block_on(load(props(&[("region_name", "us-east-1")])))
+            .err()
+            .unwrap();
+        assert_eq!(error.message, "Glue catalog property warehouse is required");
+
+        let error = runtime
+            .block_on(load(props(&[("warehouse", "gs://warehouse/glue")])))
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ElixirErrorKind::NotSupported);
+    }
+}
@@ -0,0 +1,133 @@
+use rustler::{Env, NifStruct, ResourceArc, Term};
+use std::collections::HashMap;
+
+use iceberg::CatalogBuilder;
+use iceberg_catalog_hms::{
+    HmsCatalogBuilder, HMS_CATALOG_PROP_THRIFT_TRANSPORT, HMS_CATALOG_PROP_URI,
+    HMS_CATALOG_PROP_WAREHOUSE, THRIFT_TRANSPORT_BUFFERED, THRIFT_TRANSPORT_FRAMED,
+};
+
+use crate::catalog::CatalogResource;
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::props;
+use crate::task::{reply_async, AsyncReply};
+
+#[derive(NifStruct)]
+#[module = "ExIceberg.Hms.CatalogConfig"]
+struct HmsCatalogConfig {
+    // Thrift address of the metastore, e.g. `localhost:9083`
+    address: String,
+    // Location of the tables, e.g. `s3://bucket/warehouse`
+    warehouse: String,
+    // `buffered` (the default) or `framed`
+    thrift_transport: Option<String>,
+    properties: HashMap<String, String>,
+    file_io: HashMap<String, String>,
+}
+
+/// Builds a Hive Metastore catalog from its `uri` and `warehouse` properties,
+/// the others are FileIO properties.
+///
+/// The `uri` is the metastore's thrift address, with or without pyiceberg's
+/// `thrift://` scheme. The address is resolved here but the metastore is only
+/// contacted once the catalog is used.
+pub async fn load(mut props: HashMap<String, String>) -> Result<CatalogResource, ElixirError> {
+    let address = props::required(&props, "HMS", HMS_CATALOG_PROP_URI)?;
+    props::required(&props, "HMS", HMS_CATALOG_PROP_WAREHOUSE)?;
+
+    if let Some(address) = address.strip_prefix("thrift://") {
+        props.insert(HMS_CATALOG_PROP_URI.to_string(), address.to_string());
+    }
+    match props.get(HMS_CATALOG_PROP_THRIFT_TRANSPORT) {
+        Some(transport)
+            if !transport.eq_ignore_ascii_case(THRIFT_TRANSPORT_BUFFERED)
+                && !transport.eq_ignore_ascii_case(THRIFT_TRANSPORT_FRAMED) =>
+        {
+            return Err(ElixirError::new(
+                ElixirErrorKind::InvalidArgument,
+                format!(
+                    "Invalid value for {}: {}",
+                    HMS_CATALOG_PROP_THRIFT_TRANSPORT, transport
+                ),
+            ));
+        }
+        _ => {}
+    }
+
+    // The builder resolves the address with a blocking DNS lookup and creates
+    // the thrift client, which needs the runtime's timers, before returning
+    // its future, so it is called on one of the runtime's blocking threads
+    let load =
+        tokio::task::spawn_blocking(|| HmsCatalogBuilder::default().load("ex_iceberg", props))
+            .await
+            .map_err(|e| {
+                ElixirError::new(
+                    ElixirErrorKind::Unexpected,
+                    format!("Failed to create catalog: {}", e),
+                )
+            })?;
+
+    let catalog = load
+        .await
+        .map_err(|e| ElixirError::from_iceberg("Failed to create catalog", e))?;
+
+    Ok(CatalogResource::new(catalog))
+}
+
+#[rustler::nif]
+pub fn hms_catalog_new<'a>(
+    env: Env<'a>,
+    config: HmsCatalogConfig,
+    reference: Term<'a>,
+) -> AsyncReply {
+    let mut props = config.properties;
+    props.extend(config.file_io);
+    props.insert(HMS_CATALOG_PROP_URI.to_string(), config.address);
+    props.insert(HMS_CATALOG_PROP_WAREHOUSE.to_string(), config.warehouse);
+    if let Some(transport) = config.thrift_transport {
+        props.insert(HMS_CATALOG_PROP_THRIFT_TRANSPORT.to_string(), transport);
+    }
+
+    reply_async(env, reference, async move {
+        Ok(ResourceArc::new(load(props).await?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::test_support::props;
+
+    #[test]
+    fn loads_without_contacting_the_metastore() {
+        let catalog = runtime().unwrap().block_on(load(props(&[
+            ("uri", "thrift://127.0.0.1:9083"),
+            ("warehouse", "s3://warehouse/hive"),
+            ("thrift_transport", "framed"),
+        ])));
+
+        assert!(catalog.is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let runtime = runtime().unwrap();
+
+        let error = runtime
+            .block_on(load(props(&[("warehouse", "s3://warehouse/hive")])))
+            .err()
+            .unwrap();
+        assert_eq!(error.message, "HMS catalog property uri is required");
+
+        let error = runtime
+            .block_on(load(props(&[
+                ("uri", "127.0.0.1:9083"),
+                ("warehouse", "s3://warehouse/hive"),
+                ("thrift_transport", "http"),
+            ])))
+            .err()
+            .unwrap();
+        assert_eq!(error.message, "Invalid value for thrift_transport: http");
+    }
+}
@@ -0,0 +1,156 @@
+use reqwest::{Certificate, Client, Identity, Proxy};
+use std::time::Duration;
+
+use crate::error::{ElixirError, ElixirErrorKind};
+
+// HTTP settings for the REST catalog client, timeouts are in milliseconds
+#[derive(Debug, Default, Clone)]
+pub struct HttpConfig {
+    pub connect_timeout: Option<u64>,
+    pub read_timeout: Option<u64>,
+    pub proxy: Option<String>,
+    pub cacertfile: Option<String>,
+    pub certfile: Option<String>,
+    pub keyfile: Option<String>,
+}
+
+impl HttpConfig {
+    /// Builds the client for the REST catalog, or `None` when no setting is
+    /// given and the catalog's default client can be used.
+    pub fn build_client(&self) -> Result<Option<Client>, ElixirError> {
+        if self.connect_timeout.is_none()
+            && self.read_timeout.is_none()
+            && self.proxy.is_none()
+            && self.cacertfile.is_none()
+            && self.certfile.is_none()
+            && self.keyfile.is_none()
+        {
+            return Ok(None);
+        }
+
+        let mut builder = Client::builder();
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(Duration::from_millis(connect_timeout));
+        }
+
+        if let Some(read_timeout) = self.read_timeout {
+            builder = builder.read_timeout(Duration::from_millis(read_timeout));
+        }
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy).map_err(|e| invalid_config("proxy", e))?;
+            builder = builder.proxy(proxy);
+        }
+
+        // PEM client identities need rustls, use it for custom CAs as well.
+        // It trusts the system roots, the CAs are added to them.
+        if self.cacertfile.is_some() || self.certfile.is_some() {
+            builder = builder.use_rustls_tls();
+        }
+
+        if let Some(cacertfile) = &self.cacertfile {
+            let pem = read_file("cacertfile", cacertfile)?;
+            let certificates =
+                Certificate::from_pem_bundle(&pem).map_err(|e| invalid_config("cacertfile", e))?;
+
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        match (&self.certfile, &self.keyfile) {
+            (Some(certfile), keyfile) => {
+                // The key may also be bundled in the certificate file
+                let mut pem = read_file("certfile", certfile)?;
+                if let Some(keyfile) = keyfile {
+                    pem.push(b'\n');
+                    pem.extend(read_file("keyfile", keyfile)?);
+                }
+
+                let identity =
+                    Identity::from_pem(&pem).map_err(|e| invalid_config("certfile", e))?;
+                builder = builder.identity(identity);
+            }
+            (None, Some(_)) => {
+                return Err(ElixirError::new(
+                    ElixirErrorKind::InvalidArgument,
+                    "Invalid HTTP configuration: keyfile requires certfile",
+                ));
+            }
+            (None, None) => {}
+        }
+
+        builder.build().map(Some).map_err(|e| {
+            ElixirError::new(
+                ElixirErrorKind::Unexpected,
+                format!("Failed to create HTTP client: {}", e),
+            )
+        })
+    }
+}
+
+fn read_file(option: &str, path: &str) -> Result<Vec<u8>, ElixirError> {
+    std::fs::read(path).map_err(|e| {
+        ElixirError::new(
+            ElixirErrorKind::InvalidArgument,
+            format!(
+                "Invalid HTTP configuration: cannot read {} {}: {}",
+                option, path, e
+            ),
+        )
+    })
+}
+
+fn invalid_config(option: &str, err: reqwest::Error) -> ElixirError {
+    ElixirError::new(
+        ElixirErrorKind::InvalidArgument,
+        format!("Invalid HTTP configuration: {}: {}", option, err),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_default_client() {
+        let client = HttpConfig::default().build_client().unwrap();
+        assert!(client.is_none());
+    }
+
+    #[test]
+    fn builds_client_with_timeouts_and_proxy() {
+        let config = HttpConfig {
+            connect_timeout: Some(1_000),
+            read_timeout: Some(5_000),
+            proxy: Some("http://proxy.internal:3128".to_string()),
+            ..Default::default()
+        };
+
+        assert!(config.build_client().unwrap().is_some());
+    }
+
+    #[test]
+    fn rejects_missing_ca_file() {
+        let config = HttpConfig {
+            cacertfile: Some("/nonexistent/ca.pem".to_string()),
+            ..Default::default()
+        };
+
+        let error = config.build_client().unwrap_err();
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+        assert!(error.message.contains("cacertfile"));
+    }
+
+    #[test]
+    fn rejects_key_without_certificate() {
+        let config = HttpConfig {
+            keyfile: Some("/tmp/client.key".to_string()),
+            ..Default::default()
+        };
+
+        let error = config.build_client().unwrap_err();
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+    }
+}
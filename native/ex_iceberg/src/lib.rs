@@ -1,11 +1,35 @@
 mod atoms;
+mod auth;
+mod cascade;
 mod catalog;
+mod credentials;
+mod error;
+mod file_io;
+mod glue_catalog;
+mod hms_catalog;
+mod http;
+mod memory_catalog;
+mod props;
+mod purge;
+mod rest;
+mod rest_catalog;
+mod retry;
+mod runtime;
+mod scan;
+mod signer;
+mod signing_proxy;
+mod sql_catalog;
+mod sync;
 mod table;
+mod task;
+#[cfg(test)]
+mod test_support;
 mod types;
 
 pub use atoms::*;
 pub use catalog::*;
+pub use error::*;
 pub use table::*;
 pub use types::*;
 
-rustler::init!("Elixir.ExIceberg.Nif");
+rustler::init!("Elixir.ExIceberg.Nif", load = runtime::load);
@@ -0,0 +1,84 @@
+use rustler::{Env, NifStruct, ResourceArc, Term};
+use std::collections::HashMap;
+
+use iceberg::memory::{MemoryCatalogBuilder, MEMORY_CATALOG_WAREHOUSE};
+use iceberg::CatalogBuilder;
+
+use crate::catalog::CatalogResource;
+use crate::error::ElixirError;
+use crate::file_io;
+use crate::task::{reply_async, AsyncReply};
+
+const DEFAULT_WAREHOUSE: &str = "memory://warehouse";
+
+#[derive(NifStruct)]
+#[module = "ExIceberg.Memory.CatalogConfig"]
+struct MemoryCatalogConfig {
+    // Location of the tables, e.g. `memory://warehouse` or `file:///tmp/warehouse`
+    warehouse: String,
+    file_io: HashMap<String, String>,
+}
+
+/// Builds an in-memory catalog from its `warehouse` property, the others are
+/// FileIO properties.
+///
+/// Namespaces and table pointers live in the catalog only, table metadata and
+/// data files are written to the warehouse through a FileIO shared by all
+/// tables of the catalog, so `memory://` warehouses work across tables too.
+pub async fn load(mut props: HashMap<String, String>) -> Result<CatalogResource, ElixirError> {
+    let warehouse = props
+        .entry(MEMORY_CATALOG_WAREHOUSE.to_string())
+        .or_insert_with(|| DEFAULT_WAREHOUSE.to_string())
+        .clone();
+    file_io::validate_warehouse(&warehouse, &props)?;
+
+    let catalog = MemoryCatalogBuilder::default()
+        .load("ex_iceberg", props)
+        .await
+        .map_err(|e| ElixirError::from_iceberg("Failed to create catalog", e))?;
+
+    Ok(CatalogResource::new(catalog))
+}
+
+#[rustler::nif]
+pub fn memory_catalog_new<'a>(
+    env: Env<'a>,
+    config: MemoryCatalogConfig,
+    reference: Term<'a>,
+) -> AsyncReply {
+    let mut props = config.file_io;
+    props.insert(MEMORY_CATALOG_WAREHOUSE.to_string(), config.warehouse);
+
+    reply_async(env, reference, async move {
+        Ok(ResourceArc::new(load(props).await?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::test_support;
+    use iceberg::{NamespaceIdent, TableIdent};
+
+    #[test]
+    fn creates_and_loads_tables() {
+        let catalog_resource = runtime().unwrap().block_on(load(HashMap::new())).unwrap();
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+
+        let table = runtime()
+            .unwrap()
+            .block_on(async {
+                test_support::create_table(&catalog_resource, &table_ident).await;
+                catalog_resource.load_table(&table_ident, None).await
+            })
+            .unwrap();
+
+        assert_eq!(table.identifier(), &table_ident);
+        assert!(table
+            .metadata_location()
+            .unwrap()
+            .starts_with("memory://warehouse/db/orders/metadata/"));
+    }
+}
@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+
+use crate::error::{ElixirError, ElixirErrorKind};
+
+/// Returns the `key` property, `catalog` names the catalog type in the error
+/// when it is missing, e.g. `"SQL"`.
+pub fn required(
+    props: &HashMap<String, String>,
+    catalog: &str,
+    key: &str,
+) -> Result<String, ElixirError> {
+    props.get(key).cloned().ok_or_else(|| {
+        ElixirError::new(
+            ElixirErrorKind::InvalidArgument,
+            format!("{} catalog property {} is required", catalog, key),
+        )
+    })
+}
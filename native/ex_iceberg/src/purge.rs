@@ -0,0 +1,259 @@
+use futures::stream::{self, StreamExt, TryStreamExt};
+use rustler::{NifMap, NifUnitEnum};
+use std::collections::BTreeSet;
+
+use iceberg::io::FileIO;
+use iceberg::spec::DataContentType;
+use iceberg::table::Table;
+use iceberg::TableIdent;
+
+use crate::auth::Identity;
+use crate::catalog::CatalogResource;
+use crate::error::ElixirError;
+use crate::types::ElixirTableIdent;
+
+// Files deleted at once when purging a table through FileIO
+const DELETE_CONCURRENCY: usize = 16;
+
+// Encoded as `:catalog` or `:file_io`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, NifUnitEnum)]
+pub enum PurgedBy {
+    // The catalog was asked to delete the files, e.g. REST `purgeRequested`
+    Catalog,
+    // The files were deleted here after dropping the table
+    FileIo,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, NifMap)]
+pub struct DeletedFiles {
+    pub data_files: usize,
+    pub delete_files: usize,
+    pub manifest_files: usize,
+    pub manifest_lists: usize,
+    pub metadata_files: usize,
+}
+
+#[derive(Debug, NifMap)]
+pub struct PurgeReport {
+    pub table: ElixirTableIdent,
+    pub purged_by: PurgedBy,
+    // Only known when the files were deleted through FileIO
+    pub deleted_files: Option<DeletedFiles>,
+}
+
+// Files reachable from a table's metadata, deduplicated across snapshots
+#[derive(Debug, Default)]
+struct TableFiles {
+    data: BTreeSet<String>,
+    deletes: BTreeSet<String>,
+    manifests: BTreeSet<String>,
+    manifest_lists: BTreeSet<String>,
+    metadata: BTreeSet<String>,
+}
+
+/// Drops a table and deletes its files. REST catalogs are asked to purge the
+/// table themselves, other catalogs drop it and its files are then deleted
+/// through the table's FileIO.
+pub async fn purge_table(
+    catalog_resource: &CatalogResource,
+    table_ident: &TableIdent,
+    identity: Option<&Identity>,
+) -> Result<PurgeReport, ElixirError> {
+    if let Some(rest) = catalog_resource.rest() {
+        rest.purge_table(table_ident, identity).await?;
+
+        return Ok(PurgeReport {
+            table: table_ident.clone().into(),
+            purged_by: PurgedBy::Catalog,
+            deleted_files: None,
+        });
+    }
+
+    // The files are listed before dropping, a table whose manifests cannot be
+    // read is left in the catalog
+    let table = catalog_resource.load_table(table_ident, identity).await?;
+    let files = collect_files(&table).await.map_err(purge_error)?;
+    let catalog = catalog_resource.catalog(identity).await?;
+
+    catalog_resource
+        .retry()
+        .run(false, "Failed to drop table", || {
+            catalog.drop_table(table_ident)
+        })
+        .await?;
+
+    Ok(PurgeReport {
+        table: table_ident.clone().into(),
+        purged_by: PurgedBy::FileIo,
+        deleted_files: Some(delete_files(table.file_io(), &files).await?),
+    })
+}
+
+fn purge_error(err: iceberg::Error) -> ElixirError {
+    ElixirError::from_iceberg("Failed to purge table", err)
+}
+
+/// Deletes the table's files, counting the ones that still existed.
+/// Metadata files go last, so the files left by a failed purge can still be
+/// found from the table's metadata file.
+async fn delete_files(file_io: &FileIO, files: &TableFiles) -> Result<DeletedFiles, ElixirError> {
+    let purge = async {
+        Ok(DeletedFiles {
+            data_files: delete_all(file_io, &files.data).await?,
+            delete_files: delete_all(file_io, &files.deletes).await?,
+            manifest_files: delete_all(file_io, &files.manifests).await?,
+            manifest_lists: delete_all(file_io, &files.manifest_lists).await?,
+            metadata_files: delete_all(file_io, &files.metadata).await?,
+        })
+    };
+
+    purge.await.map_err(purge_error)
+}
+
+async fn collect_files(table: &Table) -> iceberg::Result<TableFiles> {
+    let metadata = table.metadata();
+    let file_io = table.file_io();
+    let mut files = TableFiles::default();
+
+    for snapshot in metadata.snapshots() {
+        files
+            .manifest_lists
+            .insert(snapshot.manifest_list().to_string());
+        let manifest_list = snapshot.load_manifest_list(file_io, metadata).await?;
+
+        for manifest_file in manifest_list.entries() {
+            // Manifests are shared by the snapshots that follow their own
+            if !files.manifests.insert(manifest_file.manifest_path.clone()) {
+                continue;
+            }
+
+            let manifest = manifest_file.load_manifest(file_io).await?;
+            for entry in manifest.entries() {
+                let path = entry.file_path().to_string();
+                match entry.content_type() {
+                    DataContentType::Data => files.data.insert(path),
+                    _ => files.deletes.insert(path),
+                };
+            }
+        }
+    }
+
+    files
+        .metadata
+        .extend(table.metadata_location().map(str::to_string));
+    files.metadata.extend(
+        metadata
+            .metadata_log()
+            .iter()
+            .map(|log| log.metadata_file.clone()),
+    );
+    files.metadata.extend(
+        metadata
+            .statistics_iter()
+            .map(|statistics| statistics.statistics_path.clone()),
+    );
+    files.metadata.extend(
+        metadata
+            .partition_statistics_iter()
+            .map(|statistics| statistics.statistics_path.clone()),
+    );
+
+    Ok(files)
+}
+
+async fn delete_all(file_io: &FileIO, paths: &BTreeSet<String>) -> iceberg::Result<usize> {
+    // Owned paths and FileIO, so the stream's future is `Send` for any lifetime
+    stream::iter(paths.iter().cloned())
+        .map(|path| {
+            let file_io = file_io.clone();
+            async move {
+                // Deleting a missing file succeeds, it is checked first so
+                // files already gone are not counted
+                if !file_io.exists(&path).await? {
+                    return Ok(0);
+                }
+                file_io.delete(path).await.map(|()| 1)
+            }
+        })
+        .buffer_unordered(DELETE_CONCURRENCY)
+        .try_fold(0, |deleted, count| async move { Ok(deleted + count) })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::test_support;
+    use iceberg::NamespaceIdent;
+
+    #[test]
+    fn purges_tables_through_file_io() {
+        let catalog_resource = test_support::memory_catalog();
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+
+        let runtime = runtime().unwrap();
+        let table = runtime.block_on(async {
+            let catalog = catalog_resource.catalog(None).await.unwrap();
+            let table = test_support::create_table(&catalog_resource, &table_ident).await;
+            test_support::append(catalog.as_ref(), &table, vec![1, 2, 3]).await
+        });
+
+        let report = runtime
+            .block_on(purge_table(&catalog_resource, &table_ident, None))
+            .unwrap();
+
+        assert_eq!(report.purged_by, PurgedBy::FileIo);
+        assert_eq!(
+            report.deleted_files,
+            Some(DeletedFiles {
+                data_files: 1,
+                manifest_files: 1,
+                manifest_lists: 1,
+                // The memory catalog deletes the current metadata file when
+                // dropping the table, only the previous one is left
+                metadata_files: 1,
+                ..Default::default()
+            })
+        );
+
+        let exists = runtime.block_on(async {
+            let catalog = catalog_resource.catalog(None).await.unwrap();
+            let metadata_exists = table
+                .file_io()
+                .exists(table.metadata_location().unwrap())
+                .await?;
+            Ok::<_, iceberg::Error>((catalog.table_exists(&table_ident).await?, metadata_exists))
+        });
+        assert_eq!(exists.unwrap(), (false, false));
+    }
+
+    #[test]
+    fn counts_only_existing_files() {
+        let catalog_resource = test_support::memory_catalog();
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+
+        let runtime = runtime().unwrap();
+        let table = runtime.block_on(test_support::create_table(&catalog_resource, &table_ident));
+        let files = TableFiles {
+            metadata: BTreeSet::from([
+                table.metadata_location().unwrap().to_string(),
+                "memory://warehouse/db/orders/metadata/missing.metadata.json".to_string(),
+            ]),
+            ..Default::default()
+        };
+
+        let deleted = runtime
+            .block_on(delete_files(table.file_io(), &files))
+            .unwrap();
+        assert_eq!(deleted.metadata_files, 1);
+
+        // Nothing is left to delete the second time
+        let deleted = runtime
+            .block_on(delete_files(table.file_io(), &files))
+            .unwrap();
+        assert_eq!(deleted, DeletedFiles::default());
+    }
+}
@@ -0,0 +1,455 @@
+use iceberg::{NamespaceIdent, TableIdent};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use rustler::NifMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::OnceCell;
+
+use crate::credentials::StorageCredential;
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::signer::{RemoteSigner, S3SignRequest, S3SignResponse};
+
+const PATH_V1: &str = "v1";
+
+type Result<T> = std::result::Result<T, ElixirError>;
+
+// Requests to REST catalog endpoints that iceberg-rust does not expose.
+// Their errors carry the response status and the kind it maps to, and
+// `RetryPolicy` adds the failed operation to the message.
+pub struct RestClient {
+    client: Client,
+    uri: String,
+    warehouse: Option<String>,
+    // Prefix given in the catalog properties, `/v1/config` overrides it
+    prefix: Option<String>,
+    headers: HeaderMap,
+    config: OnceCell<ServerConfig>,
+}
+
+// Response of `/v1/config`, fetched once per catalog
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct ServerConfig {
+    #[serde(default)]
+    pub defaults: HashMap<String, String>,
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+    // Supported endpoints, e.g. `GET /v1/{prefix}/namespaces/{namespace}/views`,
+    // not sent by servers that predate the field
+    pub endpoints: Option<Vec<String>>,
+}
+
+// Catalog config returned to Elixir
+#[derive(Debug, NifMap)]
+pub struct ServerConfigInfo {
+    pub defaults: HashMap<String, String>,
+    pub overrides: HashMap<String, String>,
+    pub endpoints: Option<Vec<String>>,
+}
+
+impl ServerConfig {
+    pub fn info(&self) -> ServerConfigInfo {
+        ServerConfigInfo {
+            defaults: self.defaults.clone(),
+            overrides: self.overrides.clone(),
+            endpoints: self.endpoints.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct LoadCredentialsResponse {
+    storage_credentials: Vec<StorageCredential>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ListViewsResponse {
+    identifiers: Vec<TableIdent>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SignRequestBody<'a> {
+    region: &'a str,
+    #[serde(flatten)]
+    request: &'a S3SignRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ErrorModel,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorModel {
+    message: String,
+    r#type: String,
+}
+
+impl RestClient {
+    /// Builds the client from the catalog properties, using their `uri`,
+    /// `warehouse`, `prefix` and `header.*` entries.
+    pub fn new(client: Client, props: &HashMap<String, String>) -> Self {
+        // Header names and values are validated when the catalog is created
+        let headers = props
+            .iter()
+            .filter_map(|(key, value)| {
+                let name = HeaderName::try_from(key.strip_prefix("header.")?).ok()?;
+                Some((name, HeaderValue::try_from(value).ok()?))
+            })
+            .collect();
+
+        Self {
+            client,
+            uri: props.get("uri").cloned().unwrap_or_default(),
+            warehouse: props.get("warehouse").cloned(),
+            prefix: props.get("prefix").cloned(),
+            headers,
+            config: OnceCell::new(),
+        }
+    }
+
+    /// Returns the catalog config, fetching it from `/v1/config` on first use.
+    pub async fn config(&self, token: Option<&str>) -> Result<&ServerConfig> {
+        self.config
+            .get_or_try_init(|| async {
+                let url = [self.uri.as_str(), PATH_V1, "config"].join("/");
+                let mut request = self.request(Method::GET, &url, token);
+
+                if let Some(warehouse) = &self.warehouse {
+                    request = request.query(&[("warehouse", warehouse)]);
+                }
+
+                parse_response(send(request).await?).await
+            })
+            .await
+    }
+
+    /// Requests fresh storage credentials for a table from
+    /// `/v1/{prefix}/namespaces/{namespace}/tables/{table}/credentials`.
+    pub async fn load_credentials(
+        &self,
+        table_ident: &TableIdent,
+        token: Option<&str>,
+    ) -> Result<Vec<StorageCredential>> {
+        let url = self
+            .table_endpoint(table_ident, &["credentials"], token)
+            .await?;
+        let request = self.request(Method::GET, &url, token);
+
+        let response: LoadCredentialsResponse = parse_response(send(request).await?).await?;
+        Ok(response.storage_credentials)
+    }
+
+    /// Drops a table with `purgeRequested=true`, asking the catalog to delete
+    /// its data and metadata files too.
+    pub async fn purge_table(&self, table_ident: &TableIdent, token: Option<&str>) -> Result<()> {
+        let url = self.table_endpoint(table_ident, &[], token).await?;
+        let request = self
+            .request(Method::DELETE, &url, token)
+            .query(&[("purgeRequested", "true")]);
+
+        empty_response(send(request).await?).await
+    }
+
+    /// Lists the views of a namespace, following the server's page tokens.
+    pub async fn list_views(
+        &self,
+        namespace: &NamespaceIdent,
+        token: Option<&str>,
+    ) -> Result<Vec<TableIdent>> {
+        let url = self
+            .namespace_endpoint(namespace, &["views"], token)
+            .await?;
+        let mut views = Vec::new();
+        let mut page_token = None;
+
+        loop {
+            let mut request = self.request(Method::GET, &url, token);
+            if let Some(page_token) = &page_token {
+                request = request.query(&[("pageToken", page_token)]);
+            }
+
+            let response: ListViewsResponse = parse_response(send(request).await?).await?;
+            views.extend(response.identifiers);
+
+            match response.next_page_token {
+                Some(next) if !next.is_empty() => page_token = Some(next),
+                _ => return Ok(views),
+            }
+        }
+    }
+
+    /// Drops a view, iceberg-rust has no view support yet.
+    pub async fn drop_view(&self, view_ident: &TableIdent, token: Option<&str>) -> Result<()> {
+        let url = self
+            .namespace_endpoint(view_ident.namespace(), &["views", view_ident.name()], token)
+            .await?;
+        let request = self.request(Method::DELETE, &url, token);
+
+        empty_response(send(request).await?).await
+    }
+
+    /// Has the remote `signer` sign an S3 request.
+    pub async fn sign(
+        &self,
+        signer: &RemoteSigner,
+        request: &S3SignRequest,
+        token: Option<&str>,
+    ) -> Result<S3SignResponse> {
+        let body = SignRequestBody {
+            region: signer.region.as_deref().unwrap_or_default(),
+            request,
+        };
+        let request = self.request(Method::POST, &signer.url, token).json(&body);
+
+        parse_response(send(request).await?).await
+    }
+
+    async fn table_endpoint(
+        &self,
+        table_ident: &TableIdent,
+        parts: &[&str],
+        token: Option<&str>,
+    ) -> Result<String> {
+        let parts = ["tables", table_ident.name()]
+            .into_iter()
+            .chain(parts.iter().copied())
+            .collect::<Vec<_>>();
+
+        self.namespace_endpoint(table_ident.namespace(), &parts, token)
+            .await
+    }
+
+    async fn namespace_endpoint(
+        &self,
+        namespace: &NamespaceIdent,
+        parts: &[&str],
+        token: Option<&str>,
+    ) -> Result<String> {
+        // Same precedence as RestCatalog: server overrides, then the catalog
+        // properties, then server defaults
+        let config = self.config(token).await?;
+        let prefix = config
+            .overrides
+            .get("prefix")
+            .or(self.prefix.as_ref())
+            .or(config.defaults.get("prefix"));
+
+        let namespace = namespace.to_url_string();
+
+        Ok([self.uri.as_str(), PATH_V1]
+            .into_iter()
+            .chain(prefix.map(String::as_str))
+            .chain(["namespaces", &namespace])
+            .chain(parts.iter().copied())
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+
+    fn request(&self, method: Method, url: &str, token: Option<&str>) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, url)
+            .headers(self.headers.clone());
+
+        match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+async fn send(request: RequestBuilder) -> Result<Response> {
+    request.send().await.map_err(|e| ElixirError {
+        source: Some(e.to_string()),
+        ..ElixirError::new(
+            ElixirErrorKind::Unexpected,
+            "Failed to send request to rest catalog server",
+        )
+    })
+}
+
+async fn read_body(response: Response) -> Result<Vec<u8>> {
+    let bytes = response.bytes().await.map_err(|e| ElixirError {
+        source: Some(e.to_string()),
+        ..ElixirError::new(
+            ElixirErrorKind::Unexpected,
+            "Failed to read response from rest catalog server",
+        )
+    })?;
+
+    Ok(bytes.to_vec())
+}
+
+async fn parse_response<T: DeserializeOwned>(response: Response) -> Result<T> {
+    let status = response.status();
+    let bytes = read_body(response).await?;
+
+    if status != StatusCode::OK {
+        return Err(response_error(status, &bytes));
+    }
+
+    serde_json::from_slice(&bytes).map_err(|e| ElixirError {
+        source: Some(e.to_string()),
+        ..ElixirError::new(
+            ElixirErrorKind::DataInvalid,
+            format!(
+                "Failed to parse response from rest catalog server: {}",
+                String::from_utf8_lossy(&bytes)
+            ),
+        )
+    })
+}
+
+// Responses of requests that return no body, such as drops
+async fn empty_response(response: Response) -> Result<()> {
+    match response.status() {
+        StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+        status => Err(response_error(status, &read_body(response).await?)),
+    }
+}
+
+fn response_error(status: StatusCode, body: &[u8]) -> ElixirError {
+    match serde_json::from_slice::<ErrorResponse>(body) {
+        Ok(ErrorResponse { error }) => {
+            ElixirError::from_response(status.as_u16(), Some(&error.r#type), error.message)
+        }
+        Err(_) => ElixirError::from_response(
+            status.as_u16(),
+            None,
+            format!(
+                "Received response with unexpected status code {}: {}",
+                status,
+                String::from_utf8_lossy(body)
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{http_response, stub_server};
+    use iceberg::NamespaceIdent;
+
+    fn rest_client(props: &[(&str, &str)]) -> RestClient {
+        let props = props
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        RestClient::new(Client::new(), &props)
+    }
+
+    #[test]
+    fn builds_prefixed_table_endpoint() {
+        let client = rest_client(&[("uri", "http://localhost:8181/catalog")]);
+        client
+            .config
+            .set(ServerConfig {
+                overrides: HashMap::from([("prefix".to_string(), "wh-1".to_string())]),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let ident = TableIdent::new(
+            NamespaceIdent::from_strs(["db", "sales"]).unwrap(),
+            "orders".to_string(),
+        );
+        let url = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(client.table_endpoint(&ident, &["credentials"], None))
+            .unwrap();
+
+        assert_eq!(
+            url,
+            "http://localhost:8181/catalog/v1/wh-1/namespaces/db\u{1f}sales/tables/orders/credentials"
+        );
+    }
+
+    #[test]
+    fn signs_with_remote_signer() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let body = r#"{"uri":"http://minio:9000/bucket/key","headers":{"Authorization":["AWS4-HMAC-SHA256 signed"]}}"#;
+        let (addr, stub) = stub_server(&runtime, vec![http_response("200 OK", body)]);
+        let signer = RemoteSigner {
+            url: format!("http://{}/v1/aws/s3/sign", addr),
+            region: Some("local-01".to_string()),
+        };
+
+        let request = S3SignRequest {
+            method: "GET".to_string(),
+            uri: "http://minio:9000/bucket/key".to_string(),
+            headers: HashMap::new(),
+            body: None,
+        };
+        let response = runtime
+            .block_on(rest_client(&[]).sign(&signer, &request, Some("token")))
+            .unwrap();
+        let received = runtime.block_on(stub).unwrap()[0].to_lowercase();
+
+        assert_eq!(
+            response.headers["Authorization"],
+            ["AWS4-HMAC-SHA256 signed"]
+        );
+        assert!(received.contains("authorization: bearer token"));
+        assert!(received.contains(r#""region":"local-01""#));
+    }
+
+    #[test]
+    fn purges_tables() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (addr, stub) = stub_server(&runtime, vec![http_response("204 No Content", "")]);
+        let uri = format!("http://{}", addr);
+        let client = rest_client(&[("uri", &uri)]);
+        client.config.set(ServerConfig::default()).unwrap();
+
+        let ident = TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+        runtime.block_on(client.purge_table(&ident, None)).unwrap();
+        let received = runtime.block_on(stub).unwrap();
+
+        assert!(received[0]
+            .starts_with("DELETE /v1/namespaces/db/tables/orders?purgeRequested=true HTTP/1.1"));
+    }
+
+    #[test]
+    fn parses_server_config() {
+        let body = r#"{
+            "defaults": {"clients": "4"},
+            "overrides": {"prefix": "wh-1"},
+            "endpoints": ["GET /v1/{prefix}/namespaces", "POST /v1/{prefix}/transactions/commit"]
+        }"#;
+
+        let info = serde_json::from_str::<ServerConfig>(body).unwrap().info();
+
+        assert_eq!(info.overrides["prefix"], "wh-1");
+        assert_eq!(
+            info.endpoints.unwrap()[1],
+            "POST /v1/{prefix}/transactions/commit"
+        );
+
+        let config = serde_json::from_str::<ServerConfig>(r#"{"overrides": {}}"#).unwrap();
+        assert!(config.defaults.is_empty());
+        assert_eq!(config.endpoints, None);
+    }
+
+    #[test]
+    fn maps_rest_error_model() {
+        let body = br#"{"error":{"message":"Table does not exist","type":"NoSuchTableException","code":404}}"#;
+
+        let error = response_error(StatusCode::NOT_FOUND, body);
+
+        assert_eq!(error.kind, ElixirErrorKind::NoSuchTable);
+        assert_eq!(error.http_status, Some(404));
+        assert_eq!(error.message, "Table does not exist");
+
+        let error = response_error(StatusCode::SERVICE_UNAVAILABLE, b"");
+
+        assert_eq!(error.kind, ElixirErrorKind::ServiceUnavailable);
+        assert_eq!(error.http_status, Some(503));
+    }
+}
@@ -0,0 +1,993 @@
+use rustler::{Env, NifStruct, ResourceArc, Term};
+use std::collections::{HashMap, VecDeque};
+use std::panic::RefUnwindSafe;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::Duration;
+
+use iceberg::table::Table;
+use iceberg::{Catalog, CatalogBuilder, NamespaceIdent, TableIdent};
+use iceberg_catalog_rest::{RestCatalog, RestCatalogBuilder};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Client;
+
+use crate::auth::{Identity, OAuth2Config, OAuth2Session, Token, TokenInfo};
+use crate::catalog::CatalogResource;
+use crate::credentials::{self, StorageCredential, VendedCredentials};
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::http::HttpConfig;
+use crate::rest::{RestClient, ServerConfigInfo};
+use crate::retry::RetryPolicy;
+use crate::signer::{RemoteSigner, S3SignRequest, S3SignResponse};
+use crate::sync::{read_unpoisoned, write_unpoisoned};
+use crate::task::{reply_async, AsyncReply};
+
+// REST Catalog Resource for wrapping iceberg-rust RestCatalog
+//
+// The catalog is built once and shared with every table resource it creates,
+// so the HTTP client, the `/v1/config` response and the OAuth2 token are
+// reused across calls. RestCatalog initialises that state behind its own
+// async-safe cell, so a plain `Arc` is enough to share it.
+//
+// With an OAuth2 session the token is managed here instead, and the catalog
+// is rebuilt with the new token whenever the session refreshes it. Requests
+// made as another identity get their own session and catalog the same way,
+// kept for the most recently used identities.
+pub struct RestCatalogResource {
+    // Kept to rebuild the catalog with a new token
+    props: HashMap<String, String>,
+    client: Option<Client>,
+    // Token endpoint settings, also used for per-request credentials
+    oauth2: OAuth2Config,
+    session: Option<OAuth2Session>,
+    // Endpoints RestCatalog does not expose, such as vended credentials
+    rest: RestClient,
+    state: RwLock<CatalogState>,
+    // Least recently used first
+    identities: Mutex<VecDeque<(String, Arc<IdentityCatalog>)>>,
+    retry: RetryPolicy,
+}
+
+struct CatalogState {
+    // Token the catalog was built with, when it comes from the session
+    token: Option<String>,
+    catalog: Arc<RestCatalog>,
+}
+
+// Identities beyond this many are dropped, least recently used first
+const IDENTITY_CACHE_SIZE: usize = 32;
+
+// Session and catalog of an identity requests are made as
+struct IdentityCatalog {
+    auth: IdentityAuth,
+    // Built on first use and again when the token changes
+    state: RwLock<Option<CatalogState>>,
+}
+
+enum IdentityAuth {
+    Token(String),
+    Session(Box<OAuth2Session>),
+}
+
+unsafe impl Send for RestCatalogResource {}
+unsafe impl Sync for RestCatalogResource {}
+// RestCatalog only mutates its state through a OnceCell and a tokio Mutex,
+// and the token and catalog here are swapped as a whole, so nothing is left
+// half-updated if a NIF panics.
+impl RefUnwindSafe for RestCatalogResource {}
+
+impl RestCatalogResource {
+    pub async fn new(
+        uri: String,
+        warehouse: Option<String>,
+        props: HashMap<String, String>,
+        http: HttpConfig,
+        retry: RetryPolicy,
+        oauth2: OAuth2Config,
+    ) -> Result<Self, ElixirError> {
+        let mut props = props;
+
+        // Add required properties
+        props.insert("uri".to_string(), uri);
+        if let Some(warehouse) = warehouse {
+            props.insert("warehouse".to_string(), warehouse);
+        }
+
+        let client = http.build_client()?;
+
+        // Tokens are requested here when there is a grant to request them
+        // with, so they can be refreshed before they expire. A static token is
+        // handed to the session, which uses it until it expires, like the
+        // catalog does with a token and a credential.
+        let session = oauth2.has_grant().then(|| {
+            let session = OAuth2Session::new(oauth2.clone(), client.clone().unwrap_or_default());
+            if let Some(token) = props.remove("token") {
+                session.set_token(Token::from_static(token));
+            }
+            session
+        });
+
+        // The catalog fetches `/v1/config` on first use
+        let catalog = load_catalog(&props, client.clone(), None).await?;
+        let rest = RestClient::new(client.clone().unwrap_or_default(), &props);
+
+        Ok(Self {
+            props,
+            client,
+            oauth2,
+            session,
+            rest,
+            state: RwLock::new(CatalogState {
+                token: None,
+                catalog: Arc::new(catalog),
+            }),
+            identities: Mutex::new(VecDeque::new()),
+            retry,
+        })
+    }
+
+    /// Returns the catalog to run a request with. Given an `identity`, the
+    /// catalog authenticated as that identity, built when its token changes.
+    pub async fn catalog(
+        &self,
+        identity: Option<&Identity>,
+    ) -> Result<Arc<RestCatalog>, ElixirError> {
+        let Some(identity) = identity else {
+            return self.shared_catalog().await;
+        };
+
+        let identity_catalog = self.identity_catalog(identity);
+        let token = identity_catalog.token().await?;
+
+        if let Some(state) = read_unpoisoned(&identity_catalog.state).as_ref() {
+            if state.token.as_deref() == Some(token.as_str()) {
+                return Ok(state.catalog.clone());
+            }
+        }
+
+        // The new catalog fetches `/v1/config` on its first request, once for
+        // each token of the identity
+        let catalog =
+            Arc::new(load_catalog(&self.props, self.client.clone(), Some(token.clone())).await?);
+
+        *write_unpoisoned(&identity_catalog.state) = Some(CatalogState {
+            token: Some(token),
+            catalog: catalog.clone(),
+        });
+
+        Ok(catalog)
+    }
+
+    /// Returns the token to authenticate a request with, for requests made
+    /// outside of RestCatalog.
+    async fn token(&self, identity: Option<&Identity>) -> Result<Option<String>, ElixirError> {
+        match (identity, &self.session) {
+            (Some(identity), _) => self.identity_token(identity).await.map(Some),
+            (None, Some(session)) => Ok(Some(session.token().await?.access_token)),
+            (None, None) => Ok(self.props.get("token").cloned()),
+        }
+    }
+
+    async fn identity_token(&self, identity: &Identity) -> Result<String, ElixirError> {
+        self.identity_catalog(identity).token().await
+    }
+
+    // Returns the cached session and catalog of an identity, so that its
+    // token is only requested again when it expires
+    fn identity_catalog(&self, identity: &Identity) -> Arc<IdentityCatalog> {
+        let key = identity.cache_key();
+        let mut identities = self
+            .identities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(index) = identities.iter().position(|(cached, _)| *cached == key) {
+            let entry = identities.remove(index).expect("index is in bounds");
+            let identity_catalog = entry.1.clone();
+            identities.push_back(entry);
+            return identity_catalog;
+        }
+
+        let auth = match identity {
+            Identity::Token(token) => IdentityAuth::Token(token.clone()),
+            Identity::Credential(credential) => {
+                let oauth2 = OAuth2Config {
+                    credential: Some(credential.clone()),
+                    subject_token: None,
+                    actor_token: None,
+                    ..self.oauth2.clone()
+                };
+                IdentityAuth::Session(Box::new(OAuth2Session::new(
+                    oauth2,
+                    self.client.clone().unwrap_or_default(),
+                )))
+            }
+            // Exchanged on behalf of the subject, with the catalog's client
+            // credential and actor token when it has them
+            Identity::SubjectToken(subject_token) => {
+                let oauth2 = OAuth2Config {
+                    subject_token: Some(subject_token.clone()),
+                    ..self.oauth2.clone()
+                };
+                IdentityAuth::Session(Box::new(OAuth2Session::new(
+                    oauth2,
+                    self.client.clone().unwrap_or_default(),
+                )))
+            }
+        };
+
+        let identity_catalog = Arc::new(IdentityCatalog {
+            auth,
+            state: RwLock::new(None),
+        });
+
+        identities.push_back((key, identity_catalog.clone()));
+        if identities.len() > IDENTITY_CACHE_SIZE {
+            identities.pop_front();
+        }
+
+        identity_catalog
+    }
+
+    /// Returns the shared catalog, rebuilt first when the OAuth2 session has a
+    /// new token.
+    async fn shared_catalog(&self) -> Result<Arc<RestCatalog>, ElixirError> {
+        let Some(session) = &self.session else {
+            return Ok(read_unpoisoned(&self.state).catalog.clone());
+        };
+
+        let token = session.token().await?;
+
+        {
+            let state = read_unpoisoned(&self.state);
+            if state.token.as_deref() == Some(token.access_token.as_str()) {
+                return Ok(state.catalog.clone());
+            }
+        }
+
+        let catalog = Arc::new(
+            load_catalog(
+                &self.props,
+                self.client.clone(),
+                Some(token.access_token.clone()),
+            )
+            .await?,
+        );
+
+        *write_unpoisoned(&self.state) = CatalogState {
+            token: Some(token.access_token),
+            catalog: catalog.clone(),
+        };
+
+        Ok(catalog)
+    }
+
+    pub fn retry(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// HTTP client of the catalog, also used to send the requests it signs.
+    pub fn http_client(&self) -> Client {
+        self.client.clone().unwrap_or_default()
+    }
+
+    /// The server's `/v1/config` response, fetched on first use and kept for
+    /// the catalog's lifetime.
+    pub async fn server_config(
+        &self,
+        identity: Option<&Identity>,
+    ) -> Result<ServerConfigInfo, ElixirError> {
+        let token = self.token(identity).await?;
+
+        let config = self
+            .retry
+            .run(true, "Failed to load catalog config", || {
+                self.rest.config(token.as_deref())
+            })
+            .await?;
+
+        Ok(config.info())
+    }
+
+    /// Requests fresh storage credentials for a table from the catalog.
+    async fn load_credentials(
+        &self,
+        table_ident: &TableIdent,
+        identity: Option<&Identity>,
+    ) -> Result<Vec<StorageCredential>, ElixirError> {
+        let token = self.token(identity).await?;
+
+        self.retry
+            .run(true, "Failed to load credentials", || {
+                self.rest.load_credentials(table_ident, token.as_deref())
+            })
+            .await
+    }
+
+    /// Drops a table and asks the catalog to purge its files.
+    pub async fn purge_table(
+        &self,
+        table_ident: &TableIdent,
+        identity: Option<&Identity>,
+    ) -> Result<(), ElixirError> {
+        let token = self.token(identity).await?;
+
+        self.retry
+            .run(false, "Failed to purge table", || {
+                self.rest.purge_table(table_ident, token.as_deref())
+            })
+            .await
+    }
+
+    /// Lists the views of a namespace.
+    pub async fn list_views(
+        &self,
+        namespace: &NamespaceIdent,
+        identity: Option<&Identity>,
+    ) -> Result<Vec<TableIdent>, ElixirError> {
+        let token = self.token(identity).await?;
+
+        self.retry
+            .run(true, "Failed to list views", || {
+                self.rest.list_views(namespace, token.as_deref())
+            })
+            .await
+    }
+
+    /// Drops a view from the catalog.
+    pub async fn drop_view(
+        &self,
+        view_ident: &TableIdent,
+        identity: Option<&Identity>,
+    ) -> Result<(), ElixirError> {
+        let token = self.token(identity).await?;
+
+        self.retry
+            .run(false, "Failed to drop view", || {
+                self.rest.drop_view(view_ident, token.as_deref())
+            })
+            .await
+    }
+
+    /// Storage credentials the catalog vended for a loaded table.
+    pub fn vended_credentials(&self, table: &Table) -> VendedCredentials {
+        VendedCredentials::from_table(table, &self.props)
+    }
+
+    /// Returns the table with renewed vended credentials applied to its
+    /// FileIO, under the catalog's own properties. The table is reloaded
+    /// instead when the catalog returns none for its location, or has no
+    /// `/credentials` endpoint.
+    pub async fn renew_credentials(
+        &self,
+        table: &Table,
+        identity: Option<&Identity>,
+    ) -> Result<Table, ElixirError> {
+        let table_ident = table.identifier();
+        let credentials = match self.load_credentials(table_ident, identity).await {
+            Ok(credentials) => credentials,
+            // Servers that predate the endpoint answer 404 or reject it
+            Err(e)
+                if matches!(
+                    e.kind,
+                    ElixirErrorKind::NotFound | ElixirErrorKind::NotSupported
+                ) =>
+            {
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(credential) = credentials::select(credentials, table.metadata().location()) {
+            return credentials::apply(table, credential.config, &self.props);
+        }
+
+        let catalog = self.catalog(identity).await?;
+        self.retry
+            .run(true, "Failed to load table", || {
+                catalog.load_table(table_ident)
+            })
+            .await
+    }
+
+    /// Remote signer of a loaded table, `None` unless the catalog enabled
+    /// remote signing for it.
+    pub fn remote_signer(&self, table: &Table) -> Option<RemoteSigner> {
+        RemoteSigner::from_table(table, &self.props["uri"])
+    }
+
+    /// Has the table's remote signer sign an S3 request.
+    pub async fn sign_request(
+        &self,
+        signer: &RemoteSigner,
+        request: &S3SignRequest,
+        identity: Option<&Identity>,
+    ) -> Result<S3SignResponse, ElixirError> {
+        let token = self.token(identity).await?;
+
+        self.retry
+            .run(true, "Failed to sign request", || {
+                self.rest.sign(signer, request, token.as_deref())
+            })
+            .await
+    }
+
+    /// Details of the current token, `None` before the first one is fetched
+    /// or when the catalog does not use a token.
+    pub fn token_info(&self) -> Option<TokenInfo> {
+        match &self.session {
+            Some(session) => session.current().map(|token| token.info(true)),
+            None => self
+                .props
+                .get("token")
+                .map(|token| Token::from_static(token.clone()).info(false)),
+        }
+    }
+
+    /// Requests a new token and rebuilds the catalog with it.
+    pub async fn refresh_token(&self) -> Result<TokenInfo, ElixirError> {
+        let session = self.session.as_ref().ok_or_else(|| {
+            ElixirError::new(
+                ElixirErrorKind::NotSupported,
+                "Token refresh requires a credential or a subject_token",
+            )
+        })?;
+
+        let token = session.refresh().await?;
+        self.shared_catalog().await?;
+
+        Ok(token.info(true))
+    }
+}
+
+impl IdentityCatalog {
+    async fn token(&self) -> Result<String, ElixirError> {
+        match &self.auth {
+            IdentityAuth::Token(token) => Ok(token.clone()),
+            IdentityAuth::Session(session) => Ok(session.token().await?.access_token),
+        }
+    }
+}
+
+async fn load_catalog(
+    props: &HashMap<String, String>,
+    client: Option<Client>,
+    token: Option<String>,
+) -> Result<RestCatalog, ElixirError> {
+    let mut props = props.clone();
+    if let Some(token) = token {
+        props.insert("token".to_string(), token);
+    }
+
+    let mut builder = RestCatalogBuilder::default();
+    if let Some(client) = client {
+        builder = builder.with_client(client);
+    }
+
+    // Create catalog using the new builder API
+    builder
+        .load("ex_iceberg", props)
+        .await
+        .map_err(|e| ElixirError::from_iceberg("Failed to create catalog", e))
+}
+
+const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(NifStruct)]
+#[module = "ExIceberg.Rest.CatalogConfig"]
+struct CatalogConfig {
+    uri: String,
+    warehouse: Option<String>,
+    token: Option<String>,
+    credential: Option<String>,
+    oauth2_server_uri: Option<String>,
+    scope: Option<String>,
+    audience: Option<String>,
+    resource: Option<String>,
+    // HTTP client, timeouts and delays in milliseconds
+    connect_timeout: Option<u64>,
+    read_timeout: Option<u64>,
+    proxy: Option<String>,
+    cacertfile: Option<String>,
+    certfile: Option<String>,
+    keyfile: Option<String>,
+    max_retries: Option<u32>,
+    retry_base_delay: Option<u64>,
+    retry_max_delay: Option<u64>,
+    // RFC 8693 token exchange and token refresh, the margin in milliseconds
+    subject_token: Option<String>,
+    subject_token_type: Option<String>,
+    actor_token: Option<String>,
+    actor_token_type: Option<String>,
+    token_refresh_margin: Option<u64>,
+    // Passed to the catalog as is, the typed fields above take precedence
+    properties: HashMap<String, String>,
+    headers: Vec<(String, String)>,
+    // FileIO properties of the catalog's tables, e.g. `s3.endpoint`
+    file_io: HashMap<String, String>,
+}
+
+impl CatalogConfig {
+    // Reads the typed settings from pyiceberg-style catalog properties, the
+    // others are passed to the catalog as is.
+    fn from_properties(mut props: HashMap<String, String>) -> Self {
+        let mut take = |key: &str| props.remove(key);
+
+        Self {
+            uri: take("uri").unwrap_or_default(),
+            warehouse: take("warehouse"),
+            token: take("token"),
+            credential: take("credential"),
+            oauth2_server_uri: take("oauth2-server-uri"),
+            scope: take("scope"),
+            audience: take("audience"),
+            resource: take("resource"),
+            connect_timeout: None,
+            read_timeout: None,
+            proxy: None,
+            cacertfile: None,
+            certfile: None,
+            keyfile: None,
+            max_retries: None,
+            retry_base_delay: None,
+            retry_max_delay: None,
+            subject_token: None,
+            subject_token_type: None,
+            actor_token: None,
+            actor_token_type: None,
+            token_refresh_margin: None,
+            properties: props,
+            headers: Vec::new(),
+            file_io: HashMap::new(),
+        }
+    }
+}
+
+/// Builds a REST catalog from `catalog_load` properties such as `uri`,
+/// `warehouse`, `credential` or `header.*`.
+pub async fn load(props: HashMap<String, String>) -> Result<CatalogResource, ElixirError> {
+    build_catalog(CatalogConfig::from_properties(props))
+        .await
+        .map(CatalogResource::from)
+}
+
+// Extra headers are given to the REST catalog as `header.<name>` properties.
+// They are validated here, the catalog would only reject them on first use.
+fn header_property(name: String, value: String) -> Result<(String, String), ElixirError> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        ElixirError::new(
+            ElixirErrorKind::InvalidArgument,
+            format!("Invalid header {}: {}", name, e),
+        )
+    };
+
+    HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(&e))?;
+    HeaderValue::from_str(&value).map_err(|e| invalid(&e))?;
+
+    Ok((format!("header.{}", name), value))
+}
+
+#[rustler::nif]
+pub fn rest_catalog_new<'a>(
+    env: Env<'a>,
+    config: CatalogConfig,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let catalog_resource = build_catalog(config).await?;
+        Ok(ResourceArc::new(CatalogResource::from(catalog_resource)))
+    })
+}
+
+async fn build_catalog(config: CatalogConfig) -> Result<RestCatalogResource, ElixirError> {
+    let mut props = config.properties;
+    props.extend(config.file_io);
+
+    for (name, value) in config.headers {
+        let (key, value) = header_property(name, value)?;
+        props.insert(key, value);
+    }
+
+    if let Some(token) = config.token {
+        props.insert("token".to_string(), token);
+    }
+
+    let oauth2 = OAuth2Config {
+        token_endpoint: config
+            .oauth2_server_uri
+            .unwrap_or_else(|| format!("{}/v1/oauth/tokens", config.uri)),
+        credential: config.credential,
+        scope: config.scope,
+        audience: config.audience,
+        resource: config.resource,
+        subject_token: config.subject_token,
+        subject_token_type: config.subject_token_type,
+        actor_token: config.actor_token,
+        actor_token_type: config.actor_token_type,
+        refresh_margin: config
+            .token_refresh_margin
+            .map_or(DEFAULT_REFRESH_MARGIN, Duration::from_millis),
+    };
+
+    let http = HttpConfig {
+        connect_timeout: config.connect_timeout,
+        read_timeout: config.read_timeout,
+        proxy: config.proxy,
+        cacertfile: config.cacertfile,
+        certfile: config.certfile,
+        keyfile: config.keyfile,
+    };
+
+    let mut retry = RetryPolicy::default();
+    if let Some(max_retries) = config.max_retries {
+        retry.max_retries = max_retries;
+    }
+    if let Some(base_delay) = config.retry_base_delay {
+        retry.base_delay = Duration::from_millis(base_delay);
+    }
+    if let Some(max_delay) = config.retry_max_delay {
+        retry.max_delay = Duration::from_millis(max_delay);
+    }
+
+    RestCatalogResource::new(config.uri, config.warehouse, props, http, retry, oauth2).await
+}
+
+#[rustler::nif]
+pub fn rest_catalog_token_info(
+    catalog_resource: ResourceArc<CatalogResource>,
+) -> Option<TokenInfo> {
+    catalog_resource.rest()?.token_info()
+}
+
+#[rustler::nif]
+pub fn rest_catalog_refresh_token<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let catalog = catalog_resource.rest().ok_or_else(|| {
+            ElixirError::new(
+                ElixirErrorKind::NotSupported,
+                "Token refresh is only supported by REST catalogs",
+            )
+        })?;
+
+        catalog.refresh_token().await
+    })
+}
+
+#[rustler::nif]
+pub fn rest_catalog_config<'a>(
+    env: Env<'a>,
+    catalog_resource: ResourceArc<CatalogResource>,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let catalog = catalog_resource.rest().ok_or_else(|| {
+            ElixirError::new(
+                ElixirErrorKind::NotSupported,
+                "Catalog config is only supported by REST catalogs",
+            )
+        })?;
+
+        catalog.server_config(identity.as_ref()).await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::test_support::{self, http_response, stub_server};
+
+    #[test]
+    fn rest_catalog_resource_requires_uri() {
+        let result = runtime().unwrap().block_on(RestCatalogResource::new(
+            String::new(),
+            None,
+            HashMap::new(),
+            HttpConfig::default(),
+            RetryPolicy::default(),
+            OAuth2Config::default(),
+        ));
+
+        let error = result.err().unwrap();
+        assert!(error.message.contains("Catalog uri is required"));
+    }
+
+    fn catalog_with_token(token: &str) -> RestCatalogResource {
+        let props = HashMap::from([("token".to_string(), token.to_string())]);
+
+        runtime()
+            .unwrap()
+            .block_on(RestCatalogResource::new(
+                "http://localhost:8181".to_string(),
+                None,
+                props,
+                HttpConfig::default(),
+                RetryPolicy::default(),
+                OAuth2Config::default(),
+            ))
+            .unwrap()
+    }
+
+    #[test]
+    fn static_token_is_not_refreshable() {
+        let catalog = catalog_with_token("opaque-token");
+
+        let info = catalog.token_info().unwrap();
+        assert!(!info.refreshable);
+        assert_eq!(info.expires_at, None);
+
+        let error = runtime()
+            .unwrap()
+            .block_on(catalog.refresh_token())
+            .unwrap_err();
+        assert_eq!(error.kind, ElixirErrorKind::NotSupported);
+    }
+
+    #[test]
+    fn token_override_keeps_catalog_token() {
+        let catalog = catalog_with_token("opaque-token");
+        let identity = Identity::Token("tenant-token".to_string());
+
+        let request_catalog = runtime()
+            .unwrap()
+            .block_on(catalog.catalog(Some(&identity)))
+            .unwrap();
+
+        assert!(!Arc::ptr_eq(
+            &request_catalog,
+            &read_unpoisoned(&catalog.state).catalog
+        ));
+        assert_eq!(catalog.props["token"], "opaque-token");
+    }
+
+    #[test]
+    fn reuses_identity_sessions() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (addr, stub) = stub_server(
+            &runtime,
+            vec![http_response(
+                "200 OK",
+                r#"{"access_token":"tenant-token","token_type":"bearer","expires_in":3600}"#,
+            )],
+        );
+        let catalog = runtime
+            .block_on(RestCatalogResource::new(
+                "http://localhost:8181".to_string(),
+                None,
+                HashMap::new(),
+                HttpConfig::default(),
+                RetryPolicy::default(),
+                OAuth2Config {
+                    token_endpoint: format!("http://{}/v1/oauth/tokens", addr),
+                    ..OAuth2Config::default()
+                },
+            ))
+            .unwrap();
+        let identity = Identity::Credential("tenant:secret".to_string());
+
+        // The stub answers a single token request
+        let (first, second) = runtime.block_on(async {
+            let first = catalog.catalog(Some(&identity)).await.unwrap();
+            let second = catalog.catalog(Some(&identity)).await.unwrap();
+            (first, second)
+        });
+        let requests = runtime.block_on(stub).unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(requests[0].contains("grant_type=client_credentials"));
+        assert_eq!(
+            runtime.block_on(catalog.identity_token(&identity)).unwrap(),
+            "tenant-token"
+        );
+    }
+
+    #[test]
+    fn exchanges_subject_tokens() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (addr, stub) = stub_server(
+            &runtime,
+            vec![http_response(
+                "200 OK",
+                r#"{"access_token":"user-token","token_type":"bearer","expires_in":3600}"#,
+            )],
+        );
+        let catalog = runtime
+            .block_on(RestCatalogResource::new(
+                "http://localhost:8181".to_string(),
+                None,
+                HashMap::new(),
+                HttpConfig::default(),
+                RetryPolicy::default(),
+                OAuth2Config {
+                    token_endpoint: format!("http://{}/v1/oauth/tokens", addr),
+                    credential: Some("service:secret".to_string()),
+                    ..OAuth2Config::default()
+                },
+            ))
+            .unwrap();
+        let identity = Identity::SubjectToken("user-jwt".to_string());
+
+        // Exchanged once, the session then reuses the token
+        let tokens = runtime.block_on(async {
+            let first = catalog.identity_token(&identity).await.unwrap();
+            let second = catalog.identity_token(&identity).await.unwrap();
+            [first, second]
+        });
+        let requests = runtime.block_on(stub).unwrap();
+
+        assert_eq!(tokens, ["user-token", "user-token"]);
+        assert!(requests[0].contains("subject_token=user-jwt"));
+        assert!(requests[0].contains("client_id=service"));
+        assert!(requests[0]
+            .contains("grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Atoken-exchange"));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_identities() {
+        let catalog = catalog_with_token("opaque-token");
+        let identity = |i: usize| Identity::Token(format!("tenant-token-{}", i));
+
+        let first = catalog.identity_catalog(&identity(0));
+        for i in 1..IDENTITY_CACHE_SIZE {
+            catalog.identity_catalog(&identity(i));
+        }
+
+        // Using the first identity again makes the second the oldest
+        assert!(Arc::ptr_eq(&first, &catalog.identity_catalog(&identity(0))));
+        catalog.identity_catalog(&identity(IDENTITY_CACHE_SIZE));
+
+        let identities = catalog.identities.lock().unwrap();
+        assert_eq!(identities.len(), IDENTITY_CACHE_SIZE);
+        assert!(identities
+            .iter()
+            .any(|(key, _)| *key == identity(0).cache_key()));
+        assert!(!identities
+            .iter()
+            .any(|(key, _)| *key == identity(1).cache_key()));
+    }
+
+    #[test]
+    fn reloads_table_without_credentials_endpoint() {
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+        let table = runtime().unwrap().block_on(test_support::create_table(
+            &test_support::memory_catalog(),
+            &table_ident,
+        ));
+        let load_table = serde_json::json!({
+            "metadata-location": table.metadata_location(),
+            "metadata": table.metadata(),
+            "config": {"s3.access-key-id": "reloaded"},
+        })
+        .to_string();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (addr, stub) = stub_server(
+            &runtime,
+            vec![
+                http_response("200 OK", r#"{"defaults":{},"overrides":{}}"#),
+                http_response("404 Not Found", ""),
+                http_response("200 OK", r#"{"defaults":{},"overrides":{}}"#),
+                http_response("200 OK", &load_table),
+            ],
+        );
+        let catalog = runtime
+            .block_on(RestCatalogResource::new(
+                format!("http://{}", addr),
+                None,
+                HashMap::new(),
+                HttpConfig::default(),
+                RetryPolicy::default(),
+                OAuth2Config::default(),
+            ))
+            .unwrap();
+
+        let reloaded = runtime
+            .block_on(catalog.renew_credentials(&table, None))
+            .unwrap();
+        let requests = runtime.block_on(stub).unwrap();
+
+        assert!(requests[1].starts_with("GET /v1/namespaces/db/tables/orders/credentials "));
+        assert!(requests[3].starts_with("GET /v1/namespaces/db/tables/orders "));
+        assert_eq!(
+            catalog.vended_credentials(&reloaded).config["s3.access-key-id"],
+            "reloaded"
+        );
+    }
+
+    #[test]
+    fn maps_rest_catalog_errors() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (addr, stub) = stub_server(
+            &runtime,
+            vec![
+                http_response("200 OK", r#"{"defaults":{},"overrides":{}}"#),
+                http_response(
+                    "409 Conflict",
+                    r#"{"error":{"message":"Namespace already exists: db","type":"AlreadyExistsException","code":409}}"#,
+                ),
+                http_response("503 Service Unavailable", ""),
+            ],
+        );
+        let catalog_resource = runtime
+            .block_on(RestCatalogResource::new(
+                format!("http://{}", addr),
+                None,
+                HashMap::new(),
+                HttpConfig::default(),
+                RetryPolicy {
+                    max_retries: 0,
+                    ..RetryPolicy::default()
+                },
+                OAuth2Config::default(),
+            ))
+            .unwrap();
+        let namespace = NamespaceIdent::new("db".to_string());
+
+        // RestCatalog reports both as `Unexpected`, with a fixed message for
+        // the conflict and the status in the context of the other
+        let (conflict, unavailable) = runtime.block_on(async {
+            let catalog = catalog_resource.catalog(None).await.unwrap();
+            let retry = catalog_resource.retry();
+
+            let conflict = retry
+                .run(false, "Failed to create namespace", || {
+                    catalog.create_namespace(&namespace, HashMap::new())
+                })
+                .await
+                .unwrap_err();
+            let unavailable = retry
+                .run(true, "Failed to list namespaces", || {
+                    catalog.list_namespaces(None)
+                })
+                .await
+                .unwrap_err();
+
+            (conflict, unavailable)
+        });
+        runtime.block_on(stub).unwrap();
+
+        assert_eq!(conflict.kind, ElixirErrorKind::AlreadyExists);
+        assert_eq!(conflict.http_status, Some(409));
+        assert_eq!(unavailable.kind, ElixirErrorKind::ServiceUnavailable);
+        assert_eq!(unavailable.http_status, Some(503));
+    }
+
+    #[test]
+    fn rest_catalog_resource_accepts_any_warehouse_location() {
+        // Namespace and table metadata operations do not use the FileIO
+        let result = runtime().unwrap().block_on(RestCatalogResource::new(
+            "http://localhost:8181".to_string(),
+            Some("hdfs://namenode/warehouse".to_string()),
+            HashMap::new(),
+            HttpConfig::default(),
+            RetryPolicy::default(),
+            OAuth2Config::default(),
+        ));
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn header_property_prefixes_name() {
+        let (key, value) = header_property(
+            "X-Iceberg-Access-Delegation".to_string(),
+            "vended-credentials".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(key, "header.X-Iceberg-Access-Delegation");
+        assert_eq!(value, "vended-credentials");
+    }
+
+    #[test]
+    fn header_property_rejects_invalid_name() {
+        let error = header_property("bad header".to_string(), "value".to_string()).unwrap_err();
+
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+    }
+}
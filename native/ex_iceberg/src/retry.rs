@@ -0,0 +1,159 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::{ElixirError, ElixirErrorKind, OperationError};
+
+// Retries of catalog requests rejected with 429 or 5xx, with exponential
+// backoff and full jitter between attempts. Enabled by default, since only
+// requests the server did not process are retried when they are not
+// idempotent.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Runs `request`, retrying it while it fails with a retryable status.
+    /// Errors are reported with `operation` prepended to their message.
+    ///
+    /// Requests that change the catalog are not `idempotent`: they are only
+    /// retried on 429 and 503, where the server did not process them.
+    pub async fn run<F, Fut, T, E>(
+        &self,
+        idempotent: bool,
+        operation: &str,
+        mut request: F,
+    ) -> Result<T, ElixirError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: OperationError,
+    {
+        let mut attempt = 0;
+
+        loop {
+            let error = match request().await {
+                Ok(value) => return Ok(value),
+                Err(e) => e.into_elixir_error(operation),
+            };
+
+            if attempt >= self.max_retries || !is_retryable(&error, idempotent) {
+                return Err(error);
+            }
+
+            tokio::time::sleep(self.delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+
+        // Full jitter, a random factor in [0, 1)
+        backoff.mul_f64(rand::random::<f64>())
+    }
+}
+
+fn is_retryable(error: &ElixirError, idempotent: bool) -> bool {
+    if error.kind == ElixirErrorKind::CommitStateUnknown {
+        return false;
+    }
+
+    match error.http_status {
+        Some(429) | Some(503) => true,
+        Some(status) => idempotent && (500..600).contains(&status),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn http_error(status: u16) -> ElixirError {
+        ElixirError {
+            http_status: Some(status),
+            ..ElixirError::new(ElixirErrorKind::Unexpected, "Request failed")
+        }
+    }
+
+    fn iceberg_error(status: &str) -> iceberg::Error {
+        iceberg::Error::new(
+            iceberg::ErrorKind::Unexpected,
+            "Received response with unexpected status code",
+        )
+        .with_context("status", status)
+    }
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let attempts = Cell::new(0);
+
+        let result = runtime.block_on(policy(3).run(true, "Failed to load table", || async {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(iceberg_error("502 Bad Gateway"))
+            } else {
+                Ok(())
+            }
+        }));
+
+        assert!(result.is_ok());
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn only_retries_writes_when_not_processed() {
+        assert!(is_retryable(&http_error(429), false));
+        assert!(is_retryable(&http_error(503), false));
+        assert!(!is_retryable(&http_error(500), false));
+        assert!(is_retryable(&http_error(500), true));
+        assert!(!is_retryable(&http_error(404), true));
+    }
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let attempts = Cell::new(0);
+
+        let result: Result<(), _> =
+            runtime.block_on(policy(2).run(true, "Failed to load table", || async {
+                attempts.set(attempts.get() + 1);
+                Err(iceberg_error("429 Too Many Requests"))
+            }));
+
+        assert_eq!(result.unwrap_err().http_status, Some(429));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn caps_backoff() {
+        let policy = policy(10);
+        assert!(policy.delay(20) <= policy.max_delay);
+    }
+}
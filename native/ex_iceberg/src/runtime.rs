@@ -0,0 +1,111 @@
+use rustler::{Env, NifMap, Term};
+use std::sync::OnceLock;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::error::{ElixirError, ElixirErrorKind};
+
+// Settings for the shared runtime, read from `config :ex_iceberg, :runtime`
+// and passed as NIF load data by `ExIceberg.Runtime.load_data/0`.
+#[derive(Debug, Default, Clone, NifMap)]
+pub struct RuntimeConfig {
+    pub worker_threads: Option<usize>,
+    pub max_blocking_threads: Option<usize>,
+    pub thread_name: Option<String>,
+}
+
+static CONFIG: OnceLock<RuntimeConfig> = OnceLock::new();
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// NIF load hook, records the runtime settings without starting any threads.
+pub fn load(_env: Env, load_info: Term) -> bool {
+    // Load data is absent (`0`) when the library is loaded without it
+    let config = load_info.decode::<RuntimeConfig>().unwrap_or_default();
+    let _ = CONFIG.set(config);
+    true
+}
+
+/// Returns the process-wide runtime, building it on first use.
+pub fn runtime() -> Result<&'static Runtime, ElixirError> {
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+
+    let runtime = build_runtime(CONFIG.get().cloned().unwrap_or_default())?;
+
+    // Another thread may have won the race, its runtime is kept and ours dropped
+    Ok(RUNTIME.get_or_init(|| runtime))
+}
+
+fn build_runtime(config: RuntimeConfig) -> Result<Runtime, ElixirError> {
+    let mut builder = Builder::new_multi_thread();
+    builder.enable_all();
+
+    // tokio panics on zero, so reject it here
+    if let Some(worker_threads) = config.worker_threads {
+        if worker_threads == 0 {
+            return Err(invalid_config("worker_threads must be greater than 0"));
+        }
+        builder.worker_threads(worker_threads);
+    }
+
+    if let Some(max_blocking_threads) = config.max_blocking_threads {
+        if max_blocking_threads == 0 {
+            return Err(invalid_config(
+                "max_blocking_threads must be greater than 0",
+            ));
+        }
+        builder.max_blocking_threads(max_blocking_threads);
+    }
+
+    if let Some(thread_name) = config.thread_name {
+        builder.thread_name(thread_name);
+    }
+
+    builder.build().map_err(|e| {
+        ElixirError::new(
+            ElixirErrorKind::Unexpected,
+            format!("Failed to start runtime: {}", e),
+        )
+    })
+}
+
+fn invalid_config(message: &str) -> ElixirError {
+    ElixirError::new(
+        ElixirErrorKind::InvalidArgument,
+        format!("Invalid runtime configuration: {}", message),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_zero_threads() {
+        let config = RuntimeConfig {
+            worker_threads: Some(0),
+            ..Default::default()
+        };
+
+        let error = build_runtime(config).unwrap_err();
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+    }
+
+    #[test]
+    fn builds_configured_runtime() {
+        let config = RuntimeConfig {
+            worker_threads: Some(2),
+            max_blocking_threads: Some(4),
+            thread_name: Some("ex_iceberg-test".to_string()),
+        };
+
+        let runtime = build_runtime(config).unwrap();
+        let name = runtime.block_on(async {
+            tokio::spawn(async { std::thread::current().name().map(String::from) })
+                .await
+                .unwrap()
+        });
+
+        assert_eq!(name.as_deref(), Some("ex_iceberg-test"));
+    }
+}
@@ -0,0 +1,246 @@
+use rustler::{Encoder, Env, NewBinary, ResourceArc, Term};
+use std::panic::RefUnwindSafe;
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{ArrowError, Schema as ArrowSchema};
+use futures::StreamExt;
+use iceberg::arrow::schema_to_arrow_schema;
+use iceberg::scan::ArrowRecordBatchStream;
+use iceberg::table::Table;
+
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::signing_proxy::SigningProxy;
+use crate::task::{reply_async, AsyncReply};
+
+/// Record batches read by a scan, encoded as an Arrow IPC stream and
+/// returned to Elixir as a binary.
+pub struct ArrowIpcStream(Vec<u8>);
+
+impl ArrowIpcStream {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Encoder for ArrowIpcStream {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        let mut binary = NewBinary::new(env, self.0.len());
+        binary.as_mut_slice().copy_from_slice(&self.0);
+        Term::from(binary)
+    }
+}
+
+// Open scan of a table, read one record batch at a time so only the batch
+// being returned is held in memory.
+pub struct ScanResource {
+    // Schema of the selected columns, returned alone when there are no rows
+    schema: Arc<ArrowSchema>,
+    state: tokio::sync::Mutex<ScanState>,
+    // Signs the requests of the table's FileIO, for tables using remote signing
+    _signing_proxy: Option<Arc<SigningProxy>>,
+}
+
+struct ScanState {
+    batches: ArrowRecordBatchStream,
+    returned_any: bool,
+}
+
+// The stream is only polled under the async mutex, and a panic while polling
+// leaves it dropped half-read, which later reads report as errors.
+impl RefUnwindSafe for ScanResource {}
+
+#[rustler::resource_impl]
+impl rustler::Resource for ScanResource {}
+
+impl ScanResource {
+    /// Starts reading the current snapshot of the table, all columns or the
+    /// given ones. The signing proxy the table's FileIO goes through, if any,
+    /// is kept running until the scan is dropped.
+    pub async fn open(
+        table: &Table,
+        columns: Option<Vec<String>>,
+        signing_proxy: Option<Arc<SigningProxy>>,
+    ) -> Result<Self, ElixirError> {
+        let schema = Arc::new(projected_schema(table, columns.as_deref())?);
+        let builder = match &columns {
+            Some(columns) => table.scan().select(columns),
+            None => table.scan().select_all(),
+        };
+
+        let batches = builder
+            .build()
+            .map_err(|e| ElixirError::from_iceberg("Failed to scan table", e))?
+            .to_arrow()
+            .await
+            .map_err(|e| ElixirError::from_iceberg("Failed to scan table", e))?;
+
+        Ok(Self {
+            schema,
+            state: tokio::sync::Mutex::new(ScanState {
+                batches,
+                returned_any: false,
+            }),
+            _signing_proxy: signing_proxy,
+        })
+    }
+
+    /// Returns the next record batch as its own IPC stream, `None` once the
+    /// scan is done. A scan without rows returns the schema alone first.
+    pub async fn next_batch(&self) -> Result<Option<ArrowIpcStream>, ElixirError> {
+        let mut state = self.state.lock().await;
+
+        let batch = match state.batches.next().await {
+            Some(batch) => {
+                batch.map_err(|e| ElixirError::from_iceberg("Failed to scan table", e))?
+            }
+            None if state.returned_any => return Ok(None),
+            None => {
+                state.returned_any = true;
+                return encode(&self.schema, &[]).map(Some);
+            }
+        };
+
+        state.returned_any = true;
+        encode(&batch.schema(), &[batch]).map(Some)
+    }
+}
+
+fn projected_schema(table: &Table, columns: Option<&[String]>) -> Result<ArrowSchema, ElixirError> {
+    let schema = schema_to_arrow_schema(table.metadata().current_schema())
+        .map_err(|e| ElixirError::from_iceberg("Failed to scan table", e))?;
+
+    let Some(columns) = columns else {
+        return Ok(schema);
+    };
+    let projected = columns
+        .iter()
+        .map(|column| schema.index_of(column))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|indices| schema.project(&indices));
+
+    projected.map_err(|e| ElixirError::new(ElixirErrorKind::InvalidArgument, e.to_string()))
+}
+
+fn encode(schema: &ArrowSchema, batches: &[RecordBatch]) -> Result<ArrowIpcStream, ElixirError> {
+    let write = || -> Result<Vec<u8>, ArrowError> {
+        let mut writer = StreamWriter::try_new(Vec::new(), schema)?;
+        for batch in batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+        writer.into_inner()
+    };
+
+    write().map(ArrowIpcStream).map_err(|e| {
+        ElixirError::new(
+            ElixirErrorKind::Unexpected,
+            format!("Failed to encode scan: {}", e),
+        )
+    })
+}
+
+// Scan NIF functions
+#[rustler::nif]
+pub fn scan_next_batch<'a>(
+    env: Env<'a>,
+    scan_resource: ResourceArc<ScanResource>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(
+        env,
+        reference,
+        async move { scan_resource.next_batch().await },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::table::SmartTableResource;
+    use crate::test_support;
+    use arrow_array::{Array, Int64Array};
+    use arrow_ipc::reader::StreamReader;
+    use iceberg::table::StaticTable;
+    use iceberg::{NamespaceIdent, TableIdent};
+
+    fn decode(stream: &ArrowIpcStream) -> (Arc<ArrowSchema>, Vec<RecordBatch>) {
+        let reader = StreamReader::try_new(stream.as_bytes(), None).unwrap();
+        let schema = reader.schema();
+        (schema, reader.map(Result::unwrap).collect())
+    }
+
+    // Every IPC stream the scan returns, in order
+    async fn read_all(scan: ScanResource) -> Vec<ArrowIpcStream> {
+        let mut streams = Vec::new();
+        while let Some(stream) = scan.next_batch().await.unwrap() {
+            streams.push(stream);
+        }
+        streams
+    }
+
+    #[test]
+    fn scans_static_tables() {
+        let catalog_resource = test_support::memory_catalog();
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+
+        let runtime = runtime().unwrap();
+        let (empty, table) = runtime.block_on(async {
+            let catalog = catalog_resource.catalog(None).await.unwrap();
+            let empty = test_support::create_table(&catalog_resource, &table_ident).await;
+            let table = test_support::append(catalog.as_ref(), &empty, vec![1, 2, 3]).await;
+            let table = test_support::append(catalog.as_ref(), &table, vec![4, 5]).await;
+            (empty, table)
+        });
+
+        let open = |table: &Table| {
+            let static_table = runtime
+                .block_on(StaticTable::from_metadata_file(
+                    table.metadata_location().unwrap(),
+                    table_ident.clone(),
+                    table.file_io().clone(),
+                ))
+                .unwrap();
+            SmartTableResource::from_static(static_table)
+        };
+
+        // One stream per record batch, here one per appended data file
+        let table = open(&table);
+        let streams =
+            runtime.block_on(async { read_all(table.scan(None, None).await.unwrap()).await });
+        let mut ids = streams
+            .iter()
+            .flat_map(|stream| decode(stream).1)
+            .flat_map(|batch| {
+                let ids = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                ids.values().to_vec()
+            })
+            .collect::<Vec<_>>();
+        ids.sort();
+
+        assert_eq!(streams.len(), 2);
+        assert_eq!(ids, [1, 2, 3, 4, 5]);
+
+        // The snapshot before the appends has no rows but keeps the schema
+        let empty = open(&empty);
+        let streams = runtime.block_on(async {
+            let scan = empty
+                .scan(Some(vec!["id".to_string()]), None)
+                .await
+                .unwrap();
+            read_all(scan).await
+        });
+        let (schema, batches) = decode(&streams[0]);
+
+        assert_eq!(streams.len(), 1);
+        assert_eq!(schema.field(0).name(), "id");
+        assert!(batches.is_empty());
+    }
+}
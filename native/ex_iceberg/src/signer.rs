@@ -0,0 +1,106 @@
+use iceberg::table::Table;
+use rustler::NifMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Table properties of the S3 remote signer, as set by the REST catalog
+const REMOTE_SIGNING_ENABLED: &str = "s3.remote-signing-enabled";
+const SIGNER_URI: &str = "s3.signer.uri";
+const SIGNER_ENDPOINT: &str = "s3.signer.endpoint";
+const DEFAULT_SIGNER_ENDPOINT: &str = "v1/aws/s3/sign";
+
+// Signer endpoint of a table whose storage requests are signed by the catalog.
+// Used for requests signed on behalf of the caller, see
+// `SmartTableResource::sign_request`, not by the table's FileIO.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteSigner {
+    pub url: String,
+    pub region: Option<String>,
+}
+
+// S3 request to be signed, headers may have several values
+#[derive(Debug, Clone, Serialize, NifMap)]
+pub struct S3SignRequest {
+    pub method: String,
+    pub uri: String,
+    pub headers: HashMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+// Signed request returned by the signer, to be sent as is
+#[derive(Debug, Deserialize, NifMap)]
+pub struct S3SignResponse {
+    pub uri: String,
+    pub headers: HashMap<String, Vec<String>>,
+}
+
+impl RemoteSigner {
+    /// Reads the signer from the table's FileIO properties, `None` unless
+    /// remote signing is enabled. The signer defaults to the catalog at
+    /// `catalog_uri`.
+    pub fn from_table(table: &Table, catalog_uri: &str) -> Option<Self> {
+        let (_, props, _) = table.file_io().clone().into_builder().into_parts();
+        Self::from_props(&props, catalog_uri)
+    }
+
+    fn from_props(props: &HashMap<String, String>, catalog_uri: &str) -> Option<Self> {
+        let enabled = props
+            .get(REMOTE_SIGNING_ENABLED)
+            .is_some_and(|enabled| enabled.eq_ignore_ascii_case("true"));
+
+        if !enabled {
+            return None;
+        }
+
+        let uri = props.get(SIGNER_URI).map_or(catalog_uri, String::as_str);
+        let endpoint = props
+            .get(SIGNER_ENDPOINT)
+            .map_or(DEFAULT_SIGNER_ENDPOINT, String::as_str);
+
+        Some(Self {
+            url: format!(
+                "{}/{}",
+                uri.trim_end_matches('/'),
+                endpoint.trim_start_matches('/')
+            ),
+            region: props
+                .get("s3.region")
+                .or_else(|| props.get("client.region"))
+                .cloned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::props;
+
+    #[test]
+    fn defaults_to_catalog_signer() {
+        let props = props(&[
+            ("s3.remote-signing-enabled", "true"),
+            ("s3.region", "local-01"),
+        ]);
+
+        let signer = RemoteSigner::from_props(&props, "http://localhost:8181/catalog/").unwrap();
+
+        assert_eq!(signer.url, "http://localhost:8181/catalog/v1/aws/s3/sign");
+        assert_eq!(signer.region.as_deref(), Some("local-01"));
+    }
+
+    #[test]
+    fn reads_signer_properties() {
+        let props = props(&[
+            ("s3.remote-signing-enabled", "true"),
+            ("s3.signer.uri", "http://signer:8080"),
+            ("s3.signer.endpoint", "/sign"),
+        ]);
+
+        let signer = RemoteSigner::from_props(&props, "http://localhost:8181").unwrap();
+
+        assert_eq!(signer.url, "http://signer:8080/sign");
+        assert!(RemoteSigner::from_props(&HashMap::new(), "http://localhost:8181").is_none());
+    }
+}
@@ -0,0 +1,616 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::header::{CONNECTION, CONTENT_LENGTH, HOST, TRANSFER_ENCODING};
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use iceberg::table::Table;
+use reqwest::Client;
+use tokio::net::TcpListener;
+use tokio::task::{AbortHandle, JoinSet};
+use url::Url;
+
+use crate::auth::Identity;
+use crate::catalog::CatalogResource;
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::file_io;
+use crate::signer::{RemoteSigner, S3SignRequest};
+
+// FileIO properties pointing S3 requests at the proxy. Requests are sent
+// anonymously and path-style, the proxy has them signed for the real endpoint.
+const S3_ENDPOINT: &str = "s3.endpoint";
+const S3_REGION: &str = "s3.region";
+const CLIENT_REGION: &str = "client.region";
+const S3_PATH_STYLE_ACCESS: &str = "s3.path-style-access";
+const S3_CREDENTIAL_PROPS: &[&str] = &[
+    "s3.access-key-id",
+    "s3.secret-access-key",
+    "s3.session-token",
+];
+const ANONYMOUS_PROPS: &[(&str, &str)] = &[
+    ("s3.path-style-access", "true"),
+    ("s3.allow-anonymous", "true"),
+    ("s3.disable-config-load", "true"),
+    ("s3.disable-ec2-metadata", "true"),
+];
+
+// The payload is not part of the signature, as with iceberg's Java signer
+// client, so that request bodies are not sent to the signer
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+type ProxyBody = BoxBody<Bytes, reqwest::Error>;
+
+// Loopback endpoint the FileIO of tables using remote signing sends its S3
+// requests to. iceberg-rust's FileIO has no request signing hook, so each
+// request is signed by the catalog's signer here and forwarded to the
+// table's storage, and the response streamed back.
+//
+// The server stops when the proxy is dropped, along with the requests it is
+// forwarding.
+pub struct SigningProxy {
+    addr: SocketAddr,
+    routes: Arc<Mutex<Vec<Route>>>,
+    server: AbortHandle,
+}
+
+// Where the requests sent under `/{index}` are signed and forwarded to, the
+// index being the route's position
+#[derive(Clone)]
+struct Route {
+    signer: RemoteSigner,
+    // Scheme and host of the storage, e.g. `https://s3.us-east-1.amazonaws.com`
+    endpoint: String,
+    // Whether the bucket goes in the host instead of the path
+    virtual_host: bool,
+    identity: Option<Identity>,
+}
+
+impl Route {
+    fn is_same(&self, other: &Route) -> bool {
+        self.signer == other.signer
+            && self.endpoint == other.endpoint
+            && self.virtual_host == other.virtual_host
+            && self.identity.as_ref().map(Identity::cache_key)
+                == other.identity.as_ref().map(Identity::cache_key)
+    }
+
+    // URI of the request on the storage, from its path on the proxy
+    // without the route: `/bucket/key?query`
+    fn storage_uri(&self, path_and_query: &str) -> Result<String, ElixirError> {
+        if !self.virtual_host {
+            return Ok(format!("{}{}", self.endpoint, path_and_query));
+        }
+
+        let path = path_and_query.trim_start_matches('/');
+        let (bucket, rest) = path.split_at(path.find(['/', '?']).unwrap_or(path.len()));
+        let rest = rest.trim_start_matches('/');
+        let mut url = Url::parse(&self.endpoint).map_err(invalid_endpoint)?;
+        let host = format!("{}.{}", bucket, url.host_str().unwrap_or_default());
+        url.set_host(Some(&host)).map_err(invalid_endpoint)?;
+
+        Ok(format!("{}/{}", url.as_str().trim_end_matches('/'), rest))
+    }
+}
+
+impl SigningProxy {
+    /// Starts the proxy on a loopback port, signing requests with the
+    /// remote signer of `catalog`, which must be a REST catalog.
+    pub async fn start<C>(catalog: C) -> Result<Self, ElixirError>
+    where
+        C: Deref<Target = CatalogResource> + Clone + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(proxy_error)?;
+        let addr = listener.local_addr().map_err(proxy_error)?;
+        let client = catalog.rest().ok_or_else(not_rest_catalog)?.http_client();
+
+        let routes = Arc::new(Mutex::new(Vec::new()));
+        let server = tokio::spawn(serve(listener, catalog, client, routes.clone()));
+
+        Ok(Self {
+            addr,
+            routes,
+            server: server.abort_handle(),
+        })
+    }
+
+    /// Returns the table with its FileIO sending S3 requests through the
+    /// proxy, to be signed by `signer` as `identity`.
+    pub fn route(
+        &self,
+        table: &Table,
+        signer: RemoteSigner,
+        identity: Option<&Identity>,
+    ) -> Result<Table, ElixirError> {
+        let (_, mut props, _) = table.file_io().clone().into_builder().into_parts();
+        let region = props
+            .get(CLIENT_REGION)
+            .or_else(|| props.get(S3_REGION))
+            .cloned();
+
+        // Same default as opendal: path-style unless disabled, on AWS
+        // when there is no endpoint
+        let virtual_host = props
+            .get(S3_PATH_STYLE_ACCESS)
+            .is_some_and(|value| !is_truthy(value));
+        let endpoint = match props.get(S3_ENDPOINT) {
+            Some(endpoint) if endpoint.starts_with("http") => endpoint.clone(),
+            Some(endpoint) => format!("https://{}", endpoint),
+            None => match &region {
+                Some(region) => format!("https://s3.{}.amazonaws.com", region),
+                None => "https://s3.amazonaws.com".to_string(),
+            },
+        };
+
+        let index = self.add_route(Route {
+            signer,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            virtual_host,
+            identity: identity.cloned(),
+        });
+
+        for key in S3_CREDENTIAL_PROPS {
+            props.remove(*key);
+        }
+        props.extend(
+            ANONYMOUS_PROPS
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string())),
+        );
+        props.insert(
+            S3_ENDPOINT.to_string(),
+            format!("http://{}/{}", self.addr, index),
+        );
+        // opendal needs a region even though it does not sign the requests
+        props
+            .entry(S3_REGION.to_string())
+            .or_insert_with(|| region.unwrap_or_else(|| "us-east-1".to_string()));
+
+        file_io::with_props(table, props)
+            .map_err(|e| ElixirError::from_iceberg("Failed to route table storage", e))
+    }
+
+    fn add_route(&self, route: Route) -> usize {
+        let mut routes = self.routes.lock().unwrap_or_else(PoisonError::into_inner);
+
+        match routes.iter().position(|existing| existing.is_same(&route)) {
+            Some(index) => index,
+            None => {
+                routes.push(route);
+                routes.len() - 1
+            }
+        }
+    }
+}
+
+impl Drop for SigningProxy {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+// Accepts connections until aborted, aborting the ones still open with it
+async fn serve<C>(listener: TcpListener, catalog: C, client: Client, routes: Arc<Mutex<Vec<Route>>>)
+where
+    C: Deref<Target = CatalogResource> + Clone + Send + Sync + 'static,
+{
+    let mut connections = JoinSet::new();
+
+    loop {
+        while connections.try_join_next().is_some() {}
+
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let catalog = catalog.clone();
+        let client = client.clone();
+        let routes = routes.clone();
+
+        connections.spawn(async move {
+            let service = service_fn(move |request| {
+                let catalog = catalog.clone();
+                let client = client.clone();
+                let routes = routes.clone();
+                async move {
+                    let response = forward(&catalog, &client, &routes, request).await;
+                    Ok::<_, Infallible>(response.unwrap_or_else(error_response))
+                }
+            });
+
+            // Errors here are connections the FileIO dropped, it reports them
+            let _ = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+// Signs a request for the storage of its route and forwards it
+async fn forward(
+    catalog: &CatalogResource,
+    client: &Client,
+    routes: &Mutex<Vec<Route>>,
+    request: Request<Incoming>,
+) -> Result<Response<ProxyBody>, ElixirError> {
+    let (parts, body) = request.into_parts();
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map_or("/", |path_and_query| path_and_query.as_str());
+
+    let (index, path_and_query) = path_and_query
+        .trim_start_matches('/')
+        .split_once('/')
+        .ok_or_else(|| unknown_route(path_and_query))?;
+    let route = index
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| {
+            let routes = routes.lock().unwrap_or_else(PoisonError::into_inner);
+            routes.get(index).cloned()
+        })
+        .ok_or_else(|| unknown_route(index))?;
+
+    let uri = route.storage_uri(&format!("/{}", path_and_query))?;
+    let host = Url::parse(&uri)
+        .ok()
+        .and_then(|url| {
+            let host = url.host_str()?.to_string();
+            Some(match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host,
+            })
+        })
+        .ok_or_else(|| invalid_endpoint(&uri))?;
+
+    let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, value) in &parts.headers {
+        if [HOST, CONNECTION, TRANSFER_ENCODING].contains(name) {
+            continue;
+        }
+        if let Ok(value) = value.to_str() {
+            headers
+                .entry(name.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+    }
+    headers.insert("Host".to_string(), vec![host]);
+    headers.insert(
+        "x-amz-content-sha256".to_string(),
+        vec![UNSIGNED_PAYLOAD.to_string()],
+    );
+
+    let sign_request = S3SignRequest {
+        method: parts.method.to_string(),
+        uri,
+        headers,
+        body: None,
+    };
+    let rest = catalog.rest().ok_or_else(not_rest_catalog)?;
+    let signed = rest
+        .sign_request(&route.signer, &sign_request, route.identity.as_ref())
+        .await?;
+
+    let body = body
+        .collect()
+        .await
+        .map_err(|e| ElixirError::new(ElixirErrorKind::Unexpected, e.to_string()))?
+        .to_bytes();
+
+    // reqwest sets the host and content length itself, from the signed URI
+    // and the body
+    let mut forwarded = client.request(parts.method, &signed.uri).body(body);
+    for (name, values) in &signed.headers {
+        let skipped = [HOST, CONTENT_LENGTH]
+            .iter()
+            .any(|header| name.eq_ignore_ascii_case(header.as_str()));
+        if !skipped {
+            for value in values {
+                forwarded = forwarded.header(name, value);
+            }
+        }
+    }
+
+    let response = forwarded.send().await.map_err(|e| {
+        ElixirError::new(
+            ElixirErrorKind::ServiceUnavailable,
+            format!("Failed to send signed request: {}", e),
+        )
+    })?;
+
+    let mut builder = Response::builder().status(response.status());
+    for (name, value) in response.headers() {
+        if ![CONNECTION, TRANSFER_ENCODING].contains(name) {
+            builder = builder.header(name, value);
+        }
+    }
+    let body = StreamBody::new(response.bytes_stream().map_ok(Frame::data));
+
+    builder
+        .body(BodyExt::boxed(body))
+        .map_err(|e| ElixirError::new(ElixirErrorKind::Unexpected, e.to_string()))
+}
+
+// Failures to sign or forward a request, reported to the FileIO with the
+// signer's status when there is one
+fn error_response(error: ElixirError) -> Response<ProxyBody> {
+    let status = error
+        .http_status
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .unwrap_or(StatusCode::BAD_GATEWAY);
+    let body = Full::new(Bytes::from(error.message)).map_err(|never| match never {});
+
+    let mut response = Response::new(BodyExt::boxed(body));
+    *response.status_mut() = status;
+    response
+}
+
+fn is_truthy(value: &str) -> bool {
+    ["true", "t", "1", "on"].contains(&value.to_lowercase().as_str())
+}
+
+fn proxy_error(err: std::io::Error) -> ElixirError {
+    ElixirError::new(
+        ElixirErrorKind::Unexpected,
+        format!("Failed to start signing proxy: {}", err),
+    )
+}
+
+fn invalid_endpoint(err: impl std::fmt::Display) -> ElixirError {
+    ElixirError::new(
+        ElixirErrorKind::InvalidArgument,
+        format!("Invalid S3 endpoint: {}", err),
+    )
+}
+
+fn not_rest_catalog() -> ElixirError {
+    ElixirError::new(
+        ElixirErrorKind::NotSupported,
+        "Remote signing is only supported by REST catalogs",
+    )
+}
+
+fn unknown_route(route: &str) -> ElixirError {
+    ElixirError::new(
+        ElixirErrorKind::NotFound,
+        format!("No signing route for {}", route),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::OAuth2Config;
+    use crate::http::HttpConfig;
+    use crate::rest_catalog::RestCatalogResource;
+    use crate::retry::RetryPolicy;
+    use crate::runtime::runtime;
+    use crate::test_support::{self, http_response, stub_server};
+    use iceberg::io::FileIOBuilder;
+    use iceberg::{NamespaceIdent, TableIdent};
+    use reqsign::{AwsCredential, AwsV4Signer};
+    use reqwest::header::HeaderName;
+
+    fn rest_catalog(uri: String) -> Arc<CatalogResource> {
+        let props = test_support::props(&[("token", "catalog-token")]);
+        let catalog = runtime()
+            .unwrap()
+            .block_on(RestCatalogResource::new(
+                uri,
+                None,
+                props,
+                HttpConfig::default(),
+                RetryPolicy::default(),
+                OAuth2Config::default(),
+            ))
+            .unwrap();
+
+        Arc::new(catalog.into())
+    }
+
+    // Table in a memory catalog whose FileIO is set up for S3 with `props`,
+    // as loaded from a REST catalog using remote signing
+    fn s3_table(props: &[(&str, &str)]) -> Table {
+        let catalog_resource = test_support::memory_catalog();
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+        let table = runtime()
+            .unwrap()
+            .block_on(test_support::create_table(&catalog_resource, &table_ident));
+
+        let file_io = FileIOBuilder::new("s3")
+            .with_props(test_support::props(props))
+            .build()
+            .unwrap();
+        Table::builder()
+            .identifier(table_ident)
+            .metadata(table.metadata_ref())
+            .file_io(file_io)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn signs_file_io_requests() {
+        let runtime = runtime().unwrap();
+        let (storage_addr, storage) =
+            stub_server(runtime, vec![http_response("200 OK", "iceberg")]);
+        let signed = format!(
+            r#"{{"uri":"http://{}/bucket/data/file.txt","headers":{{"Authorization":["AWS4-HMAC-SHA256 Credential=signed"],"x-amz-content-sha256":["UNSIGNED-PAYLOAD"]}}}}"#,
+            storage_addr
+        );
+        let (signer_addr, signer) = stub_server(runtime, vec![http_response("200 OK", &signed)]);
+
+        let table = s3_table(&[
+            ("s3.endpoint", &format!("http://{}", storage_addr)),
+            ("s3.region", "local-01"),
+            ("s3.remote-signing-enabled", "true"),
+            ("s3.access-key-id", "static"),
+            ("s3.secret-access-key", "static"),
+        ]);
+        let catalog_uri = format!("http://{}", signer_addr);
+        let remote_signer = RemoteSigner::from_table(&table, &catalog_uri).unwrap();
+        let catalog = rest_catalog(catalog_uri);
+
+        let content = runtime.block_on(async {
+            let proxy = SigningProxy::start(catalog).await.unwrap();
+            let table = proxy.route(&table, remote_signer, None).unwrap();
+
+            table
+                .file_io()
+                .new_input("s3://bucket/data/file.txt")
+                .unwrap()
+                .read()
+                .await
+                .unwrap()
+        });
+        assert_eq!(content.as_ref(), b"iceberg");
+
+        // The signer is asked to sign the request for the storage endpoint,
+        // which gets the signed headers and not the static credentials
+        let sign_request = runtime.block_on(signer).unwrap().remove(0);
+        assert!(sign_request.contains("authorization: Bearer catalog-token"));
+        assert!(sign_request.contains(r#""region":"local-01""#));
+        assert!(sign_request.contains(r#""method":"GET""#));
+        assert!(sign_request.contains(&format!(
+            r#""uri":"http://{}/bucket/data/file.txt""#,
+            storage_addr
+        )));
+
+        let storage_request = runtime.block_on(storage).unwrap().remove(0);
+        assert!(storage_request.starts_with("GET /bucket/data/file.txt HTTP/1.1"));
+        assert!(storage_request.contains("authorization: AWS4-HMAC-SHA256 Credential=signed"));
+        assert!(!storage_request.contains("static"));
+    }
+
+    #[test]
+    fn addresses_virtual_host_buckets() {
+        let route = Route {
+            signer: RemoteSigner {
+                url: "http://localhost:8181/v1/aws/s3/sign".to_string(),
+                region: None,
+            },
+            endpoint: "https://s3.us-east-1.amazonaws.com".to_string(),
+            virtual_host: true,
+            identity: None,
+        };
+
+        assert_eq!(
+            route.storage_uri("/bucket/data/file.txt").unwrap(),
+            "https://bucket.s3.us-east-1.amazonaws.com/data/file.txt"
+        );
+        assert_eq!(
+            route.storage_uri("/bucket?list-type=2").unwrap(),
+            "https://bucket.s3.us-east-1.amazonaws.com/?list-type=2"
+        );
+    }
+
+    // Remote signer signing requests with MinIO's root credentials, as a
+    // catalog does with the credentials of the table's storage
+    async fn minio_signer() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(
+                    hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service_fn(sign_for_minio)),
+                );
+            }
+        });
+
+        addr
+    }
+
+    async fn sign_for_minio(
+        request: Request<Incoming>,
+    ) -> Result<Response<Full<Bytes>>, Infallible> {
+        let body = request.into_body().collect().await.unwrap().to_bytes();
+        let sign: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let mut signed = reqwest::Request::new(
+            sign["method"].as_str().unwrap().parse().unwrap(),
+            sign["uri"].as_str().unwrap().parse().unwrap(),
+        );
+        for (name, values) in sign["headers"].as_object().unwrap() {
+            for value in values.as_array().unwrap() {
+                signed.headers_mut().append(
+                    HeaderName::try_from(name.as_str()).unwrap(),
+                    value.as_str().unwrap().parse().unwrap(),
+                );
+            }
+        }
+
+        let credential = AwsCredential {
+            access_key_id: "minio-root-user".to_string(),
+            secret_access_key: "minio-root-password".to_string(),
+            session_token: None,
+            expires_in: None,
+        };
+        AwsV4Signer::new("s3", sign["region"].as_str().unwrap())
+            .sign(&mut signed, &credential)
+            .unwrap();
+
+        let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, value) in signed.headers() {
+            headers
+                .entry(name.to_string())
+                .or_default()
+                .push(value.to_str().unwrap().to_string());
+        }
+        let response = serde_json::json!({"uri": signed.url().as_str(), "headers": headers});
+
+        Ok(Response::new(Full::new(Bytes::from(response.to_string()))))
+    }
+
+    #[test]
+    #[ignore = "needs MinIO from docker-compose.yml on localhost:9000"]
+    fn reads_and_writes_minio_through_signer() {
+        let table = s3_table(&[
+            ("s3.endpoint", "http://localhost:9000"),
+            ("s3.region", "us-east-1"),
+            ("s3.path-style-access", "true"),
+            ("s3.remote-signing-enabled", "true"),
+        ]);
+        let path = format!(
+            "s3://examples/ex_iceberg/signing/{}.txt",
+            std::process::id()
+        );
+
+        let runtime = runtime().unwrap();
+        let signer_addr = runtime.block_on(minio_signer());
+        let catalog_uri = format!("http://{}", signer_addr);
+        let remote_signer = RemoteSigner::from_table(&table, &catalog_uri).unwrap();
+        let catalog = rest_catalog(catalog_uri);
+
+        runtime.block_on(async {
+            let proxy = SigningProxy::start(catalog).await.unwrap();
+            let file_io = proxy
+                .route(&table, remote_signer, None)
+                .unwrap()
+                .file_io()
+                .clone();
+
+            let output = file_io.new_output(&path).unwrap();
+            output.write(b"iceberg".to_vec().into()).await.unwrap();
+            let content = file_io.new_input(&path).unwrap().read().await.unwrap();
+            file_io.delete(&path).await.unwrap();
+
+            assert_eq!(content.as_ref(), b"iceberg");
+            assert!(!file_io.exists(&path).await.unwrap());
+        });
+    }
+}
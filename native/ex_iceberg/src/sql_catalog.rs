@@ -0,0 +1,217 @@
+use rustler::{Env, NifStruct, ResourceArc, Term};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use iceberg_catalog_sql::{SqlBindStyle, SqlCatalog, SqlCatalogConfig};
+
+use crate::catalog::CatalogResource;
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::file_io;
+use crate::props;
+use crate::task::{reply_async, AsyncReply};
+
+// Connection pool properties read by SqlCatalog, which panics on values it
+// cannot parse
+const POOL_MAX_CONNECTIONS: &str = "pool.max-connections";
+const POOL_IDLE_TIMEOUT: &str = "pool.idle-timeout";
+const POOL_TEST_BEFORE_ACQUIRE: &str = "pool.test-before-acquire";
+
+#[derive(NifStruct)]
+#[module = "ExIceberg.Sql.CatalogConfig"]
+struct SqlCatalogConfigNif {
+    name: String,
+    // SQLite database, e.g. `sqlite:///var/lib/iceberg/catalog.db?mode=rwc`
+    uri: String,
+    // Location of the tables, e.g. `file:///var/lib/iceberg/warehouse`
+    warehouse: String,
+    properties: HashMap<String, String>,
+    file_io: HashMap<String, String>,
+}
+
+/// Connects to the SQLite database at the `uri` property, creating the
+/// catalog tables if needed. Tables are stored under the catalog's `name`,
+/// so several catalogs can share a database.
+///
+/// Namespaces and table pointers are stored in the database, table metadata
+/// and data files in the `warehouse`, so both outlive the catalog. The other
+/// properties configure the connection pool and the FileIO.
+pub async fn load(props: HashMap<String, String>) -> Result<CatalogResource, ElixirError> {
+    let name = props::required(&props, "SQL", "name")?;
+    let uri = props::required(&props, "SQL", "uri")?;
+    let warehouse = props::required(&props, "SQL", "warehouse")?;
+
+    if !uri.starts_with("sqlite:") {
+        return Err(ElixirError::new(
+            ElixirErrorKind::InvalidArgument,
+            format!("Unsupported SQL catalog uri, expected sqlite: {}", uri),
+        ));
+    }
+    validate_pool_props(&props)?;
+
+    let config = SqlCatalogConfig::builder()
+        .uri(uri)
+        .name(name)
+        .file_io(file_io::build(&warehouse, &props)?)
+        .warehouse_location(warehouse)
+        .sql_bind_style(SqlBindStyle::QMark)
+        .props(props)
+        .build();
+
+    let catalog = SqlCatalog::new(config)
+        .await
+        .map_err(|e| ElixirError::from_iceberg("Failed to create catalog", e))?;
+
+    Ok(CatalogResource::new(catalog))
+}
+
+fn validate_pool_props(props: &HashMap<String, String>) -> Result<(), ElixirError> {
+    validate_prop::<u32>(props, POOL_MAX_CONNECTIONS)?;
+    validate_prop::<u64>(props, POOL_IDLE_TIMEOUT)?;
+    validate_prop::<bool>(props, POOL_TEST_BEFORE_ACQUIRE)
+}
+
+fn validate_prop<T: FromStr>(
+    props: &HashMap<String, String>,
+    key: &str,
+) -> Result<(), ElixirError> {
+    match props.get(key) {
+        Some(value) if value.parse::<T>().is_err() => Err(ElixirError::new(
+            ElixirErrorKind::InvalidArgument,
+            format!("Invalid value for {}: {}", key, value),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[rustler::nif]
+pub fn sql_catalog_new<'a>(
+    env: Env<'a>,
+    config: SqlCatalogConfigNif,
+    reference: Term<'a>,
+) -> AsyncReply {
+    let mut props = config.properties;
+    props.extend(config.file_io);
+    props.insert("name".to_string(), config.name);
+    props.insert("uri".to_string(), config.uri);
+    props.insert("warehouse".to_string(), config.warehouse);
+
+    reply_async(env, reference, async move {
+        Ok(ResourceArc::new(load(props).await?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::test_support::{self, props};
+    use iceberg::{NamespaceIdent, TableIdent};
+
+    #[test]
+    fn persists_tables_in_sqlite() {
+        let dir = std::env::temp_dir().join(format!("ex_iceberg_sql_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let uri = format!("sqlite://{}/catalog.db?mode=rwc", dir.display());
+        let warehouse = format!("file://{}/warehouse", dir.display());
+        let new_catalog = || {
+            runtime()
+                .unwrap()
+                .block_on(load(props(&[
+                    ("name", "test"),
+                    ("uri", &uri),
+                    ("warehouse", &warehouse),
+                ])))
+                .unwrap()
+        };
+
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+
+        let catalog_resource = new_catalog();
+        runtime()
+            .unwrap()
+            .block_on(test_support::create_table(&catalog_resource, &table_ident));
+
+        // A new catalog on the same database sees the table
+        let table = runtime()
+            .unwrap()
+            .block_on(new_catalog().load_table(&table_ident, None))
+            .unwrap();
+
+        assert_eq!(table.identifier(), &table_ident);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn maps_sql_catalog_errors() {
+        let dir =
+            std::env::temp_dir().join(format!("ex_iceberg_sql_errors_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let uri = format!("sqlite://{}/catalog.db?mode=rwc", dir.display());
+        let catalog_resource = runtime()
+            .unwrap()
+            .block_on(load(props(&[
+                ("name", "test"),
+                ("uri", &uri),
+                ("warehouse", "memory://warehouse"),
+            ])))
+            .unwrap();
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+        let namespace = table_ident.namespace();
+
+        // The catalog raises these as `Unexpected`, only their message tells
+        // them apart
+        let error = runtime()
+            .unwrap()
+            .block_on(async {
+                test_support::create_table(&catalog_resource, &table_ident).await;
+                let catalog = catalog_resource.catalog(None).await?;
+                let error = catalog
+                    .create_namespace(namespace, HashMap::new())
+                    .await
+                    .unwrap_err();
+                assert_eq!(
+                    ElixirError::from_iceberg("Failed to create namespace", error).kind,
+                    ElixirErrorKind::AlreadyExists
+                );
+
+                catalog_resource
+                    .retry()
+                    .run(false, "Failed to drop namespace", || {
+                        catalog.drop_namespace(namespace)
+                    })
+                    .await
+            })
+            .unwrap_err();
+
+        assert_eq!(error.kind, ElixirErrorKind::NamespaceNotEmpty);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let new_catalog = |entries: &[(&str, &str)]| {
+            let mut props = props(entries);
+            props.insert("name".to_string(), "test".to_string());
+            props.insert("warehouse".to_string(), "memory://warehouse".to_string());
+            runtime().unwrap().block_on(load(props))
+        };
+
+        let error = new_catalog(&[]).err().unwrap();
+        assert_eq!(error.message, "SQL catalog property uri is required");
+
+        let error = new_catalog(&[("uri", "postgres://localhost/iceberg")])
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ElixirErrorKind::InvalidArgument);
+
+        let error = new_catalog(&[("uri", "sqlite::memory:"), ("pool.max-connections", "many")])
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.message,
+            "Invalid value for pool.max-connections: many"
+        );
+    }
+}
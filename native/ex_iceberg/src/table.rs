@@ -1,97 +1,174 @@
-use rustler::{Atom, ResourceArc};
+use rustler::{Atom, Env, ResourceArc, Term};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use tokio::runtime::Runtime;
+use std::panic::RefUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 
-use iceberg::table::Table;
-use iceberg::{Catalog, CatalogBuilder, NamespaceIdent, TableIdent};
-use iceberg_catalog_rest::{RestCatalog, RestCatalogBuilder};
+use iceberg::table::{StaticTable, Table};
+use iceberg::{NamespaceIdent, TableIdent};
 
 use crate::atoms;
+use crate::auth::Identity;
+use crate::catalog::CatalogResource;
+use crate::credentials::{VendedCredentials, VendedCredentialsInfo};
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::file_io;
+use crate::rest_catalog::RestCatalogResource;
+use crate::scan::ScanResource;
+use crate::signer::{S3SignRequest, S3SignResponse};
+use crate::signing_proxy::SigningProxy;
+use crate::sync::{read_unpoisoned, write_unpoisoned};
+use crate::task::{reply_async, AsyncReply};
+use crate::types::ElixirTableIdent;
 
-// Smart Table Resource that can recreate Table instances as needed
-// while maintaining RefUnwindSafe compatibility
+// Smart Table Resource holding the Table (metadata + FileIO) returned by the
+// catalog. Every call works against this snapshot until it is refreshed
+// explicitly, invalidated, or replaced after a commit.
 pub struct SmartTableResource {
-    // RefUnwindSafe components for recreating catalog/table
-    uri: String,
-    warehouse: Option<String>,
-    props: HashMap<String, String>,
-    namespace: String,
-    table_name: String,
-    runtime: Arc<Runtime>,
-
-    // Cached metadata - only updated when explicitly invalidated
-    metadata_cache: Arc<Mutex<Option<HashMap<String, String>>>>,
+    // Catalog resource that created this table, kept alive by this reference.
+    // None for static tables read straight from a metadata file.
+    catalog: Option<ResourceArc<CatalogResource>>,
+    table_ident: TableIdent,
+
+    table: RwLock<Table>,
+    // Set by invalidate_cache so the next access reloads the table
+    stale: AtomicBool,
+    // Started on the first read of a table using remote signing
+    signing_proxy: tokio::sync::OnceCell<Arc<SigningProxy>>,
 }
 
 unsafe impl Send for SmartTableResource {}
 unsafe impl Sync for SmartTableResource {}
+// See RestCatalogResource: the shared catalog is safe to observe after a panic.
+impl RefUnwindSafe for SmartTableResource {}
 
 #[rustler::resource_impl]
 impl rustler::Resource for SmartTableResource {}
 
 impl SmartTableResource {
-    pub fn new(
-        uri: String,
-        warehouse: Option<String>,
-        props: HashMap<String, String>,
-        namespace: String,
-        table_name: String,
-        runtime: Arc<Runtime>,
-    ) -> Self {
+    pub fn new(catalog: ResourceArc<CatalogResource>, table: Table) -> Self {
+        Self::build(Some(catalog), table)
+    }
+
+    /// Wraps a read-only table that has no catalog, it is never reloaded.
+    pub fn from_static(table: StaticTable) -> Self {
+        Self::build(None, table.into_table())
+    }
+
+    fn build(catalog: Option<ResourceArc<CatalogResource>>, table: Table) -> Self {
         Self {
-            uri,
-            warehouse,
-            props,
-            namespace,
-            table_name,
-            runtime,
-            metadata_cache: Arc::new(Mutex::new(None)),
+            catalog,
+            table_ident: table.identifier().clone(),
+            table: RwLock::new(table),
+            stale: AtomicBool::new(false),
+            signing_proxy: tokio::sync::OnceCell::new(),
         }
     }
 
-    fn build_table_ident(&self) -> TableIdent {
-        let namespace_parts: Vec<&str> = self.namespace.split('.').collect();
-        let namespace_ident =
-            NamespaceIdent::from_vec(namespace_parts.iter().map(|s| s.to_string()).collect())
-                .unwrap();
-        TableIdent::new(namespace_ident, self.table_name.clone())
+    pub fn table_ident(&self) -> &TableIdent {
+        &self.table_ident
     }
 
-    async fn get_catalog(&self) -> iceberg::Result<RestCatalog> {
-        let mut props = self.props.clone();
-        props.insert("uri".to_string(), self.uri.clone());
-        if let Some(warehouse) = &self.warehouse {
-            props.insert("warehouse".to_string(), warehouse.clone());
+    /// Returns the current table, reloading it first if it was invalidated
+    /// and renewing its vended credentials when they are about to expire.
+    pub async fn get_table(&self, identity: Option<&Identity>) -> Result<Table, ElixirError> {
+        if self.stale.load(Ordering::Acquire) {
+            self.refresh(identity).await?;
         }
 
-        RestCatalogBuilder::default()
-            .load("ex_iceberg", props)
-            .await
+        let table = self.current_table();
+        if self.vended_credentials().is_expiring() {
+            self.refresh_credentials(identity).await?;
+            return Ok(self.current_table());
+        }
+
+        Ok(table)
     }
 
-    fn get_table(&self) -> Result<Table, String> {
-        let table_ident = self.build_table_ident();
+    fn current_table(&self) -> Table {
+        read_unpoisoned(&self.table).clone()
+    }
 
-        self.runtime
-            .block_on(async {
-                let catalog = self.get_catalog().await?;
-                catalog.load_table(&table_ident).await
-            })
-            .map_err(|e| format!("Failed to load table: {}", e))
+    /// Replaces the held table, e.g. with the one returned by a commit.
+    pub fn set_table(&self, table: Table) {
+        *write_unpoisoned(&self.table) = table;
+        self.stale.store(false, Ordering::Release);
     }
 
-    pub fn get_metadata_cached(&self) -> Result<HashMap<String, String>, String> {
-        // Check cache first
-        {
-            let cache = self.metadata_cache.lock().unwrap();
-            if let Some(metadata) = cache.as_ref() {
-                return Ok(metadata.clone());
-            }
-        }
+    /// Reloads the table from the catalog.
+    pub async fn refresh(&self, identity: Option<&Identity>) -> Result<(), ElixirError> {
+        let catalog = self.catalog.as_ref().ok_or_else(|| {
+            ElixirError::new(
+                ElixirErrorKind::NotSupported,
+                "Static tables cannot be reloaded",
+            )
+        })?;
+        let table = catalog.load_table(&self.table_ident, identity).await?;
+
+        self.set_table(table);
+        Ok(())
+    }
+
+    /// Storage credentials vended for the table, empty when the catalog did
+    /// not vend any.
+    pub fn vended_credentials(&self) -> VendedCredentials {
+        self.rest_catalog()
+            .map(|catalog| catalog.vended_credentials(&self.current_table()))
+            .unwrap_or_default()
+    }
+
+    /// Renews the vended credentials through the catalog's `/credentials`
+    /// endpoint, or reloads the table when the catalog returns none for its
+    /// location or has no such endpoint.
+    pub async fn refresh_credentials(
+        &self,
+        identity: Option<&Identity>,
+    ) -> Result<VendedCredentials, ElixirError> {
+        let catalog = self.rest_catalog().ok_or_else(|| {
+            ElixirError::new(
+                ElixirErrorKind::NotSupported,
+                "Credential vending is not supported by this catalog",
+            )
+        })?;
+
+        let table = catalog
+            .renew_credentials(&self.current_table(), identity)
+            .await?;
+        self.set_table(table);
+
+        Ok(self.vended_credentials())
+    }
+
+    /// Has the catalog sign an S3 request for the table's storage, for tables
+    /// using remote signing instead of vended credentials.
+    pub async fn sign_request(
+        &self,
+        request: S3SignRequest,
+        identity: Option<&Identity>,
+    ) -> Result<S3SignResponse, ElixirError> {
+        let table = self.get_table(identity).await?;
+        let signer = self
+            .rest_catalog()
+            .and_then(|catalog| Some((catalog, catalog.remote_signer(&table)?)));
+        let (catalog, signer) = signer.ok_or_else(|| {
+            ElixirError::new(
+                ElixirErrorKind::NotSupported,
+                "Remote signing is not enabled for this table",
+            )
+        })?;
 
-        // Cache miss - fetch fresh data
-        let table = self.get_table()?;
+        catalog.sign_request(&signer, &request, identity).await
+    }
+
+    fn rest_catalog(&self) -> Option<&RestCatalogResource> {
+        self.catalog.as_ref()?.rest()
+    }
+
+    pub async fn get_metadata(
+        &self,
+        identity: Option<&Identity>,
+    ) -> Result<HashMap<String, String>, ElixirError> {
+        let table = self.get_table(identity).await?;
         let metadata = table.metadata();
 
         let mut response = HashMap::new();
@@ -131,17 +208,14 @@ impl SmartTableResource {
         let props_json = serde_json::to_string(properties).unwrap_or_else(|_| "{}".to_string());
         response.insert("properties".to_string(), props_json);
 
-        // Update cache
-        {
-            let mut cache = self.metadata_cache.lock().unwrap();
-            *cache = Some(response.clone());
-        }
-
         Ok(response)
     }
 
-    pub fn get_inspect_data(&self) -> Result<HashMap<String, String>, String> {
-        let table = self.get_table()?;
+    pub async fn get_inspect_data(
+        &self,
+        identity: Option<&Identity>,
+    ) -> Result<HashMap<String, String>, ElixirError> {
+        let table = self.get_table(identity).await?;
         let metadata = table.metadata();
         let mut response = HashMap::new();
 
@@ -167,55 +241,185 @@ impl SmartTableResource {
         Ok(response)
     }
 
+    /// Returns the current table to read or write its files with. For tables
+    /// using remote signing, its FileIO sends S3 requests through the signing
+    /// proxy, returned as well so that it runs while the files are read.
+    async fn file_io_table(
+        &self,
+        identity: Option<&Identity>,
+    ) -> Result<(Table, Option<Arc<SigningProxy>>), ElixirError> {
+        let table = self.get_table(identity).await?;
+        let signer = self
+            .rest_catalog()
+            .and_then(|catalog| catalog.remote_signer(&table));
+        let (Some(catalog), Some(signer)) = (&self.catalog, signer) else {
+            return Ok((table, None));
+        };
+
+        let signing_proxy = self
+            .signing_proxy
+            .get_or_try_init(|| async { SigningProxy::start(catalog.clone()).await.map(Arc::new) })
+            .await?
+            .clone();
+        let table = signing_proxy.route(&table, signer, identity)?;
+
+        Ok((table, Some(signing_proxy)))
+    }
+
+    /// Opens a scan of the table's current snapshot, see `ScanResource::open`.
+    pub async fn scan(
+        &self,
+        columns: Option<Vec<String>>,
+        identity: Option<&Identity>,
+    ) -> Result<ScanResource, ElixirError> {
+        let (table, signing_proxy) = self.file_io_table(identity).await?;
+
+        ScanResource::open(&table, columns, signing_proxy).await
+    }
+
+    /// Marks the table for reloading on next access, static tables are left
+    /// as they are since their metadata file does not change.
     pub fn invalidate_cache(&self) {
-        let mut cache = self.metadata_cache.lock().unwrap();
-        *cache = None;
+        if self.catalog.is_some() {
+            self.stale.store(true, Ordering::Release);
+        }
     }
 }
 
 // Table NIF functions
 #[rustler::nif]
-pub fn table_metadata(
+pub fn static_table_from_metadata<'a>(
+    env: Env<'a>,
+    metadata_location: String,
+    table_ident: Option<ElixirTableIdent>,
+    file_io_props: HashMap<String, String>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        // Same default identifier as pyiceberg's StaticTable
+        let table_ident = match table_ident {
+            Some(table_ident) => table_ident.try_into()?,
+            None => TableIdent::new(
+                NamespaceIdent::new("static-table".to_string()),
+                metadata_location.clone(),
+            ),
+        };
+        let file_io = file_io::build(&metadata_location, &file_io_props)?;
+
+        let table = StaticTable::from_metadata_file(&metadata_location, table_ident, file_io)
+            .await
+            .map_err(|e| ElixirError::from_iceberg("Failed to load static table", e))?;
+
+        Ok(ResourceArc::new(SmartTableResource::from_static(table)))
+    })
+}
+
+#[rustler::nif]
+pub fn table_metadata<'a>(
+    env: Env<'a>,
     table_resource: ResourceArc<SmartTableResource>,
-) -> (Atom, HashMap<String, String>) {
-    match table_resource.get_metadata_cached() {
-        Ok(metadata) => (atoms::ok(), metadata),
-        Err(e) => {
-            let mut error_response = HashMap::new();
-            error_response.insert("error".to_string(), e);
-            (atoms::error(), error_response)
-        }
-    }
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        table_resource.get_metadata(identity.as_ref()).await
+    })
 }
 
 #[rustler::nif]
-pub fn table_metadata_ref(
+pub fn table_metadata_ref<'a>(
+    env: Env<'a>,
     table_resource: ResourceArc<SmartTableResource>,
-) -> (Atom, HashMap<String, String>) {
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
     // For now, return the same as metadata since we're focusing on basic info
     // In the future, this could return a more efficient reference
-    match table_resource.get_metadata_cached() {
-        Ok(metadata) => (atoms::ok(), metadata),
-        Err(e) => {
-            let mut error_response = HashMap::new();
-            error_response.insert("error".to_string(), e);
-            (atoms::error(), error_response)
-        }
-    }
+    reply_async(env, reference, async move {
+        table_resource.get_metadata(identity.as_ref()).await
+    })
 }
 
 #[rustler::nif]
-pub fn table_inspect(
+pub fn table_inspect<'a>(
+    env: Env<'a>,
     table_resource: ResourceArc<SmartTableResource>,
-) -> (Atom, HashMap<String, String>) {
-    match table_resource.get_inspect_data() {
-        Ok(inspect_data) => (atoms::ok(), inspect_data),
-        Err(e) => {
-            let mut error_response = HashMap::new();
-            error_response.insert("error".to_string(), e);
-            (atoms::error(), error_response)
-        }
-    }
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        table_resource.get_inspect_data(identity.as_ref()).await
+    })
+}
+
+#[rustler::nif]
+pub fn table_scan<'a>(
+    env: Env<'a>,
+    table_resource: ResourceArc<SmartTableResource>,
+    columns: Option<Vec<String>>,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let scan = table_resource.scan(columns, identity.as_ref()).await?;
+        Ok(ResourceArc::new(scan))
+    })
+}
+
+#[rustler::nif]
+pub fn table_identifier(table_resource: ResourceArc<SmartTableResource>) -> ElixirTableIdent {
+    table_resource.table_ident().clone().into()
+}
+
+#[rustler::nif]
+pub fn table_vended_credentials(
+    table_resource: ResourceArc<SmartTableResource>,
+) -> Option<VendedCredentialsInfo> {
+    let credentials = table_resource.vended_credentials();
+    (!credentials.is_empty()).then(|| credentials.info())
+}
+
+#[rustler::nif]
+pub fn table_refresh_credentials<'a>(
+    env: Env<'a>,
+    table_resource: ResourceArc<SmartTableResource>,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        let credentials = table_resource
+            .refresh_credentials(identity.as_ref())
+            .await?;
+        Ok((!credentials.is_empty()).then(|| credentials.info()))
+    })
+}
+
+#[rustler::nif]
+pub fn table_sign_request<'a>(
+    env: Env<'a>,
+    table_resource: ResourceArc<SmartTableResource>,
+    request: S3SignRequest,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        table_resource
+            .sign_request(request, identity.as_ref())
+            .await
+    })
+}
+
+#[rustler::nif]
+pub fn table_refresh<'a>(
+    env: Env<'a>,
+    table_resource: ResourceArc<SmartTableResource>,
+    identity: Option<Identity>,
+    reference: Term<'a>,
+) -> AsyncReply {
+    reply_async(env, reference, async move {
+        table_resource.refresh(identity.as_ref()).await?;
+        Ok(atoms::ok())
+    })
 }
 
 #[rustler::nif]
@@ -223,3 +427,41 @@ pub fn table_invalidate_cache(table_resource: ResourceArc<SmartTableResource>) -
     table_resource.invalidate_cache();
     atoms::ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::runtime;
+    use crate::test_support;
+
+    #[test]
+    fn static_tables_are_read_only() {
+        let catalog_resource = test_support::memory_catalog();
+        let table_ident =
+            TableIdent::new(NamespaceIdent::new("db".to_string()), "orders".to_string());
+
+        let runtime = runtime().unwrap();
+        let table = runtime
+            .block_on(async {
+                let table = test_support::create_table(&catalog_resource, &table_ident).await;
+
+                StaticTable::from_metadata_file(
+                    table.metadata_location().unwrap(),
+                    table_ident.clone(),
+                    table.file_io().clone(),
+                )
+                .await
+            })
+            .unwrap();
+
+        let resource = SmartTableResource::from_static(table);
+        resource.invalidate_cache();
+        let table = runtime.block_on(resource.get_table(None)).unwrap();
+        assert!(table.readonly());
+        assert_eq!(resource.table_ident(), &table_ident);
+        assert!(resource.vended_credentials().is_empty());
+
+        let error = runtime.block_on(resource.refresh(None)).err().unwrap();
+        assert_eq!(error.kind, ElixirErrorKind::NotSupported);
+    }
+}
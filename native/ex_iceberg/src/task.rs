@@ -0,0 +1,122 @@
+use rustler::env::OwnedEnv;
+use rustler::{Atom, Encoder, Env, LocalPid, Monitor, ResourceArc, Term};
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::task::AbortHandle;
+
+use crate::atoms;
+use crate::error::{ElixirError, ElixirErrorKind};
+use crate::runtime::runtime;
+
+/// Return type of NIFs started with `reply_async`.
+pub type AsyncReply = Result<ResourceArc<TaskHandle>, ElixirError>;
+
+// Handle to an in-flight NIF task. The task is aborted when the handle is
+// cancelled, garbage collected, or when the process that started it exits.
+pub struct TaskHandle {
+    abort_handle: AbortHandle,
+    // Set under the lock before aborting, and checked under the lock before
+    // replying, so no reply is sent once `cancel` has returned.
+    cancelled: Arc<Mutex<bool>>,
+}
+
+#[rustler::resource_impl]
+impl rustler::Resource for TaskHandle {
+    fn down<'a>(&'a self, _env: Env<'a>, _pid: LocalPid, _monitor: Monitor) {
+        self.cancel();
+    }
+}
+
+impl TaskHandle {
+    pub fn cancel(&self) {
+        let mut cancelled = self
+            .cancelled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *cancelled = true;
+        self.abort_handle.abort();
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Runs `future` on the shared tokio runtime and sends `{reference, result}` to the
+/// calling process when it completes, so the NIF returns immediately instead
+/// of blocking a BEAM scheduler on network I/O.
+///
+/// `result` is encoded as `{:ok, value}` or `{:error, %ExIceberg.Error{}}`.
+/// No reply is sent if the returned handle cancels the task first.
+pub fn reply_async<'a, F, T>(env: Env<'a>, reference: Term<'a>, future: F) -> AsyncReply
+where
+    F: Future<Output = Result<T, ElixirError>> + Send + 'static,
+    T: Encoder + Send + 'static,
+{
+    let pid = env.pid();
+    let runtime = runtime()?;
+
+    let mut owned_env = OwnedEnv::new();
+    let saved_reference = owned_env.save(reference);
+    let cancelled = Arc::new(Mutex::new(false));
+
+    // Running the work in its own task turns a panic into a JoinError, so the
+    // caller always gets a reply.
+    let task = runtime.spawn(future);
+    let handle = ResourceArc::new(TaskHandle {
+        abort_handle: task.abort_handle(),
+        cancelled: cancelled.clone(),
+    });
+
+    // If the caller exits it will never read the reply, stop the work instead
+    handle.monitor(Some(env), &pid);
+
+    runtime.spawn(async move {
+        let result = match task.await {
+            Ok(result) => result,
+            Err(e) if e.is_cancelled() => return,
+            Err(e) => Err(ElixirError::new(
+                ElixirErrorKind::Unexpected,
+                format!("NIF task failed: {}", e),
+            )),
+        };
+
+        let cancelled = cancelled.lock().unwrap_or_else(PoisonError::into_inner);
+        if !*cancelled {
+            // The caller may have exited in the meantime, nothing to do then
+            let _ = owned_env.send_and_clear(&pid, |env| (saved_reference.load(env), result));
+        }
+    });
+
+    Ok(handle)
+}
+
+#[rustler::nif]
+pub fn task_cancel(handle: ResourceArc<TaskHandle>) -> Atom {
+    handle.cancel();
+    atoms::ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn dropping_handle_aborts_task() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let task = runtime.spawn(async { tokio::time::sleep(Duration::from_secs(60)).await });
+        let cancelled = Arc::new(Mutex::new(false));
+
+        drop(TaskHandle {
+            abort_handle: task.abort_handle(),
+            cancelled: cancelled.clone(),
+        });
+
+        let error = runtime.block_on(task).unwrap_err();
+        assert!(error.is_cancelled());
+        assert!(*cancelled.lock().unwrap());
+    }
+}
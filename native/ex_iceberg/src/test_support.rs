@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use arrow_array::{Int64Array, RecordBatch};
+use iceberg::arrow::schema_to_arrow_schema;
+use iceberg::spec::{DataFileFormat, NestedField, PrimitiveType, Schema, Type};
+use iceberg::table::Table;
+use iceberg::transaction::{ApplyTransactionAction, Transaction};
+use iceberg::writer::base_writer::data_file_writer::DataFileWriterBuilder;
+use iceberg::writer::file_writer::location_generator::{
+    DefaultFileNameGenerator, DefaultLocationGenerator,
+};
+use iceberg::writer::file_writer::ParquetWriterBuilder;
+use iceberg::writer::{IcebergWriter, IcebergWriterBuilder};
+use iceberg::{Catalog, TableCreation, TableIdent};
+use parquet::file::properties::WriterProperties;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::task::JoinHandle;
+
+use crate::catalog::CatalogResource;
+use crate::memory_catalog;
+use crate::runtime::runtime;
+
+// Fixtures shared by the tests of the catalog backends and table operations
+
+/// Properties map from `(key, value)` pairs.
+pub fn props(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Schema with a single required `id` column.
+pub fn schema() -> Schema {
+    Schema::builder()
+        .with_fields(vec![NestedField::required(
+            1,
+            "id",
+            Type::Primitive(PrimitiveType::Long),
+        )
+        .into()])
+        .build()
+        .unwrap()
+}
+
+/// In-memory catalog with a `memory://` warehouse.
+pub fn memory_catalog() -> CatalogResource {
+    runtime()
+        .unwrap()
+        .block_on(memory_catalog::load(HashMap::new()))
+        .unwrap()
+}
+
+/// Creates an empty table with `schema()`, and its namespace if needed.
+pub async fn create_table(catalog_resource: &CatalogResource, table_ident: &TableIdent) -> Table {
+    let catalog = catalog_resource.catalog(None).await.unwrap();
+    let namespace = table_ident.namespace();
+
+    if !catalog.namespace_exists(namespace).await.unwrap() {
+        catalog
+            .create_namespace(namespace, HashMap::new())
+            .await
+            .unwrap();
+    }
+
+    let creation = TableCreation::builder()
+        .name(table_ident.name().to_string())
+        .schema(schema())
+        .build();
+
+    catalog.create_table(namespace, creation).await.unwrap()
+}
+
+/// Appends rows with the given ids to a table with `schema()`.
+pub async fn append(catalog: &dyn Catalog, table: &Table, ids: Vec<i64>) -> Table {
+    let schema = Arc::new(schema_to_arrow_schema(table.metadata().current_schema()).unwrap());
+    let batch = RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(ids))]).unwrap();
+
+    let parquet_writer = ParquetWriterBuilder::new(
+        WriterProperties::default(),
+        table.metadata().current_schema().clone(),
+        None,
+        table.file_io().clone(),
+        DefaultLocationGenerator::new(table.metadata().clone()).unwrap(),
+        // A prefix per snapshot, so that later appends get new file names
+        DefaultFileNameGenerator::new(
+            format!("data-{}", table.metadata().snapshots().count()),
+            None,
+            DataFileFormat::Parquet,
+        ),
+    );
+    let mut writer = DataFileWriterBuilder::new(parquet_writer, None, 0)
+        .build()
+        .await
+        .unwrap();
+    writer.write(batch).await.unwrap();
+    let data_files = writer.close().await.unwrap();
+
+    let transaction = Transaction::new(table);
+    let transaction = transaction
+        .fast_append()
+        .add_data_files(data_files)
+        .apply(transaction)
+        .unwrap();
+    transaction.commit(catalog).await.unwrap()
+}
+
+/// HTTP/1.1 response with a JSON `body`, closing the connection after it.
+pub fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Stub server answering one request per connection with each of
+/// `responses` in turn, the handle returns the requests it received.
+pub fn stub_server(
+    runtime: &tokio::runtime::Runtime,
+    responses: Vec<String>,
+) -> (SocketAddr, JoinHandle<Vec<String>>) {
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let handle = runtime.spawn(async move {
+        let mut requests = Vec::new();
+
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buffer = [0; 4096];
+
+            // Headers, then as many body bytes as their content-length says
+            let request_len = loop {
+                if let Some(end) = received.windows(4).position(|w| w == b"\r\n\r\n") {
+                    let headers = String::from_utf8_lossy(&received[..end]).to_lowercase();
+                    let content_length = headers
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .map_or(0, |value| value.trim().parse::<usize>().unwrap());
+                    break end + 4 + content_length;
+                }
+                let read = socket.read(&mut buffer).await.unwrap();
+                assert!(read > 0, "connection closed before the request ended");
+                received.extend_from_slice(&buffer[..read]);
+            };
+            while received.len() < request_len {
+                let read = socket.read(&mut buffer).await.unwrap();
+                assert!(read > 0, "connection closed before the request ended");
+                received.extend_from_slice(&buffer[..read]);
+            }
+
+            socket.write_all(response.as_bytes()).await.unwrap();
+            requests.push(String::from_utf8_lossy(&received).to_string());
+        }
+
+        requests
+    });
+
+    (addr, handle)
+}
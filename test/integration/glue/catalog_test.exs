@@ -0,0 +1,62 @@
+defmodule ExIceberg.Glue.CatalogIntegrationTest do
+  use ExUnit.Case, async: true
+
+  alias ExIceberg.Glue.Catalog
+  alias ExIceberg.{NamespaceIdent, Table, TableIdent}
+
+  @moduletag :integration
+
+  # Glue is served by moto, table files are written to MinIO
+  @config %{
+    warehouse: "s3://examples/glue",
+    endpoint: "http://localhost:5000",
+    region: "us-east-1",
+    access_key_id: "testing",
+    secret_access_key: "testing",
+    file_io: %{
+      "s3.endpoint" => "http://localhost:9000",
+      "s3.access-key-id" => "minio-root-user",
+      "s3.secret-access-key" => "minio-root-password",
+      "s3.path-style-access" => true
+    }
+  }
+
+  defp generate_unique_name(base) do
+    hash = :crypto.strong_rand_bytes(8) |> Base.encode16() |> String.downcase()
+    "#{base}_#{hash}"
+  end
+
+  test "manages namespaces and tables" do
+    catalog = Catalog.new("glue", @config)
+    namespace = NamespaceIdent.new(generate_unique_name("glue_ns"))
+    table_ident = TableIdent.new(namespace, "orders")
+    fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+    assert {:ok, catalog, ^namespace} = Catalog.create_namespace(catalog, namespace)
+    assert {:ok, catalog, namespaces} = Catalog.list_namespaces(catalog)
+    assert namespace in namespaces
+
+    assert {:ok, catalog, %Table{}} = Catalog.create_table(catalog, table_ident, fields)
+    assert {:ok, catalog, true} = Catalog.table_exists?(catalog, table_ident)
+    assert {:ok, catalog, table} = Catalog.load_table(catalog, table_ident)
+    assert Table.identifier(table) == table_ident
+
+    assert {:ok, catalog, ^table_ident} = Catalog.drop_table(catalog, table_ident)
+    assert {:ok, _catalog, false} = Catalog.table_exists?(catalog, table_ident)
+  end
+
+  test "is loaded from pyiceberg-style properties" do
+    catalog =
+      ExIceberg.Catalog.load("glue", %{
+        "type" => "glue",
+        "warehouse" => "s3://examples/glue",
+        "glue.endpoint" => "http://localhost:5000",
+        "glue.region" => "us-east-1",
+        "glue.access-key-id" => "testing",
+        "glue.secret-access-key" => "testing"
+      })
+
+    assert {:ok, _catalog, namespaces} = ExIceberg.Catalog.list_namespaces(catalog)
+    assert is_list(namespaces)
+  end
+end
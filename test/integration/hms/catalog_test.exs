@@ -0,0 +1,51 @@
+defmodule ExIceberg.Hms.CatalogIntegrationTest do
+  use ExUnit.Case, async: true
+
+  alias ExIceberg.Hms.Catalog
+  alias ExIceberg.{NamespaceIdent, Table, TableIdent}
+
+  @moduletag :integration
+
+  # The metastore runs in a container and creates table directories on its
+  # own filesystem, metadata files are written to the same path on this host
+  @config %{
+    address: "localhost:9083",
+    warehouse: "file:///tmp/ex_iceberg/hive"
+  }
+
+  defp generate_unique_name(base) do
+    hash = :crypto.strong_rand_bytes(8) |> Base.encode16() |> String.downcase()
+    "#{base}_#{hash}"
+  end
+
+  test "manages namespaces and tables" do
+    catalog = Catalog.new("hive", @config)
+    namespace = NamespaceIdent.new(generate_unique_name("hive_ns"))
+    table_ident = TableIdent.new(namespace, "orders")
+    fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+    assert {:ok, catalog, ^namespace} = Catalog.create_namespace(catalog, namespace)
+    assert {:ok, catalog, namespaces} = Catalog.list_namespaces(catalog)
+    assert namespace in namespaces
+
+    assert {:ok, catalog, %Table{}} = Catalog.create_table(catalog, table_ident, fields)
+    assert {:ok, catalog, true} = Catalog.table_exists?(catalog, table_ident)
+    assert {:ok, catalog, table} = Catalog.load_table(catalog, table_ident)
+    assert Table.identifier(table) == table_ident
+
+    assert {:ok, catalog, ^table_ident} = Catalog.drop_table(catalog, table_ident)
+    assert {:ok, _catalog, false} = Catalog.table_exists?(catalog, table_ident)
+  end
+
+  test "is loaded from pyiceberg-style properties" do
+    catalog =
+      ExIceberg.Catalog.load("hive", %{
+        "type" => "hive",
+        "uri" => "thrift://localhost:9083",
+        "warehouse" => "file:///tmp/ex_iceberg/hive"
+      })
+
+    assert {:ok, _catalog, namespaces} = ExIceberg.Catalog.list_namespaces(catalog)
+    assert is_list(namespaces)
+  end
+end
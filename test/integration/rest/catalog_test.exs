@@ -201,8 +201,47 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
 
       {:error, %Catalog{} = updated_catalog, reason} = Catalog.list_namespaces(catalog)
       assert %Catalog{} = updated_catalog
-      assert is_binary(reason)
-      assert String.contains?(reason, "Failed to list namespaces")
+      assert %ExIceberg.Error{} = reason
+      assert String.contains?(reason.message, "Failed to list namespaces")
+    end
+  end
+
+  describe "config/2" do
+    test "returns the server's config and endpoints" do
+      catalog = Catalog.new("test_catalog", @oauth2_config)
+
+      assert {:ok, catalog, %{defaults: defaults, overrides: overrides, endpoints: endpoints}} =
+               Catalog.config(catalog)
+
+      assert is_map(defaults)
+      assert is_map(overrides)
+      assert endpoints == nil or "GET /v1/{prefix}/namespaces" in endpoints
+      assert {:ok, _catalog, %{endpoints: ^endpoints}} = Catalog.config(catalog)
+    end
+  end
+
+  describe "OAuth2 token lifecycle" do
+    test "reports and refreshes the catalog token" do
+      catalog = Catalog.new("test_catalog", @oauth2_config)
+
+      assert {:ok, catalog, nil} = Catalog.token_info(catalog)
+      assert {:ok, catalog, _namespaces} = Catalog.list_namespaces(catalog)
+
+      assert {:ok, catalog, %{expires_at: %DateTime{} = expires_at, refreshable: true}} =
+               Catalog.token_info(catalog)
+
+      assert DateTime.compare(expires_at, DateTime.utc_now()) == :gt
+
+      assert {:ok, catalog, %{expires_at: %DateTime{}}} = Catalog.refresh_token(catalog)
+      assert {:ok, _catalog, _namespaces} = Catalog.list_namespaces(catalog)
+    end
+
+    test "reports invalid client credentials" do
+      config = %{@oauth2_config | credential: "exiceberg:wrong-secret"}
+      catalog = Catalog.new("test_catalog", config)
+
+      assert {:error, _catalog, %ExIceberg.Error{kind: :unauthorized, http_status: 401}} =
+               Catalog.list_namespaces(catalog)
     end
   end
 
@@ -230,8 +269,8 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
         Catalog.create_namespace(catalog, namespace, %{})
 
       assert %Catalog{} = updated_catalog
-      assert is_binary(reason)
-      assert String.contains?(reason, "Failed to create namespace")
+      assert %ExIceberg.Error{} = reason
+      assert String.contains?(reason.message, "Failed to create namespace")
     end
 
     test "handles duplicate namespace creation" do
@@ -245,11 +284,7 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
         Catalog.create_namespace(updated_catalog, namespace, %{})
 
       assert %Catalog{} = final_catalog
-      assert is_binary(reason)
-
-      assert String.contains?(reason, "409") or
-               String.contains?(reason, "conflict") or
-               String.contains?(reason, "already exists")
+      assert %ExIceberg.Error{kind: :already_exists} = reason
     end
   end
 
@@ -276,6 +311,8 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
       # Test load_table
       {:ok, catalog, table} = Catalog.load_table(catalog, table_ident)
       assert %ExIceberg.Table{} = table
+      assert :ok = ExIceberg.Table.refresh(table)
+      assert ExIceberg.Table.identifier(table) == table_ident
 
       # Test rename_table
       new_table_ident = TableIdent.new(namespace, new_table_name)
@@ -294,6 +331,12 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
       # Verify original table no longer exists
       {:ok, catalog, false} = Catalog.table_exists?(catalog, table_ident)
 
+      # The loaded table keeps its snapshot, but can no longer be refreshed
+      assert %{"location" => _} = ExIceberg.Table.metadata(table)
+
+      assert {:error, %ExIceberg.Error{kind: :no_such_table}} =
+               ExIceberg.Table.refresh(table)
+
       # Verify renamed table exists
       {:ok, catalog, true} = Catalog.table_exists?(catalog, new_table_ident)
 
@@ -380,6 +423,35 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
       {:ok, _catalog, _} = Catalog.drop_table(catalog, table_ident)
     end
 
+    test "vended storage credentials" do
+      namespace = NamespaceIdent.new(generate_unique_name("vended_test"))
+      table_ident = TableIdent.new(namespace, SimpleSchema.__table_name__())
+
+      config =
+        Map.put(@oauth2_config, :headers, [
+          {"X-Iceberg-Access-Delegation", "vended-credentials"}
+        ])
+
+      catalog = Catalog.new("test_catalog", config)
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, namespace, %{})
+      {:ok, catalog, _} = SimpleSchema.create_table(catalog, table_ident, %{})
+      {:ok, catalog, table} = Catalog.load_table(catalog, table_ident)
+
+      assert %{config: %{"s3.access-key-id" => _}} = ExIceberg.Table.vended_credentials(table)
+
+      assert {:ok, %{config: %{"s3.session-token" => _}}} =
+               ExIceberg.Table.refresh_credentials(table)
+
+      # The warehouse vends credentials rather than signing requests
+      request = %{method: "GET", uri: "http://localhost:9000/examples/file"}
+
+      assert {:error, %ExIceberg.Error{kind: :not_supported}} =
+               ExIceberg.Table.sign_request(table, request)
+
+      {:ok, _catalog, _} = Catalog.drop_table(catalog, table_ident)
+    end
+
     test "load_table fails for non-existent table" do
       namespace_name = generate_unique_name("load_test")
       namespace = NamespaceIdent.new(namespace_name)
@@ -389,8 +461,8 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
       {:ok, catalog, _} = Catalog.create_namespace(catalog, namespace, %{})
       table_ident = TableIdent.new(namespace, table_name)
       {:error, _updated_catalog, reason} = Catalog.load_table(catalog, table_ident)
-      assert is_binary(reason)
-      assert String.contains?(reason, "Failed to load table")
+      assert %ExIceberg.Error{kind: :no_such_table} = reason
+      assert String.contains?(reason.message, "Failed to load table")
     end
 
     test "table_exists returns false for non-existent table" do
@@ -418,8 +490,8 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
       {:error, _updated_catalog, reason} =
         Catalog.rename_table(catalog, src_table_ident, dest_table_ident)
 
-      assert is_binary(reason)
-      assert String.contains?(reason, "Failed to rename table")
+      assert %ExIceberg.Error{kind: :no_such_table} = reason
+      assert String.contains?(reason.message, "Failed to rename table")
     end
 
     test "rename_table can move table between namespaces" do
@@ -471,7 +543,7 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
 
       table_ident = TableIdent.new(namespace, table_name)
       {:error, _catalog, reason} = Catalog.table_exists?(catalog, table_ident)
-      assert is_binary(reason)
+      assert %ExIceberg.Error{kind: :unexpected} = reason
     end
   end
 
@@ -535,7 +607,7 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
       {:error, _catalog, reason} =
         PrimitiveTypesSchema.create_table(catalog, table_ident, %{"test" => "primitive_types"})
 
-      assert is_binary(reason)
+      assert %ExIceberg.Error{} = reason
     end
   end
 
@@ -571,8 +643,8 @@ defmodule ExIceberg.Rest.CatalogIntegrationTest do
 
       {:error, %Catalog{} = updated_catalog, reason} = Catalog.list_namespaces(catalog)
       assert %Catalog{} = updated_catalog
-      assert is_binary(reason)
-      assert String.contains?(reason, "Failed to list namespaces")
+      assert %ExIceberg.Error{} = reason
+      assert String.contains?(reason.message, "Failed to list namespaces")
     end
   end
 end
@@ -0,0 +1,222 @@
+defmodule ExIceberg.CatalogTest do
+  use ExUnit.Case, async: true
+
+  alias ExIceberg.Catalog
+  alias ExIceberg.{NamespaceIdent, Table, TableIdent}
+
+  describe "load/2" do
+    @tag :tmp_dir
+    test "loads a catalog of each local type", %{tmp_dir: tmp_dir} do
+      for properties <- [
+            %{type: "memory"},
+            %{
+              type: "sql",
+              uri: "sqlite://#{tmp_dir}/catalog.db?mode=rwc",
+              warehouse: "file://#{tmp_dir}/warehouse"
+            }
+          ] do
+        catalog = Catalog.load("local", properties)
+        table_ident = TableIdent.from_string("shop.orders")
+        fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+        assert {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+        assert {:ok, catalog, %Table{}} = Catalog.create_table(catalog, table_ident, fields)
+        assert {:ok, catalog, [%NamespaceIdent{parts: ["shop"]}]} =
+                 Catalog.list_namespaces(catalog)
+        assert {:ok, _catalog, table} = Catalog.load_table(catalog, table_ident)
+        assert Table.identifier(table) == table_ident
+      end
+    end
+
+    test "loads a REST catalog from pyiceberg-style properties" do
+      catalog =
+        Catalog.load("rest", %{
+          "type" => "rest",
+          "uri" => "http://localhost:8181",
+          "token" => "my-token",
+          "header.X-Iceberg-Access-Delegation" => "vended-credentials"
+        })
+
+      assert catalog.type == "rest"
+      assert is_reference(catalog.nif_catalog_resource)
+    end
+
+    test "raises for an unsupported type" do
+      assert_raise ExIceberg.Error, ~r/Unsupported catalog type: nessie/, fn ->
+        Catalog.load("test", %{"type" => "nessie"})
+      end
+    end
+
+    test "raises without a type" do
+      assert_raise ArgumentError, fn -> Catalog.load("test", %{}) end
+    end
+  end
+
+  describe "drop_table/3" do
+    @tag :tmp_dir
+    test "purges the table files through FileIO", %{tmp_dir: tmp_dir} do
+      catalog = Catalog.load("local", %{type: "memory", warehouse: "file://#{tmp_dir}"})
+      table_ident = TableIdent.from_string("shop.orders")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+      {:ok, catalog, _table} = Catalog.create_table(catalog, table_ident, fields)
+      assert [_metadata_file] = Path.wildcard("#{tmp_dir}/**/*.metadata.json")
+
+      assert {:ok, catalog, report} = Catalog.drop_table(catalog, table_ident, purge: true)
+
+      assert %{
+               table: ^table_ident,
+               purged_by: :file_io,
+               deleted_files: %{data_files: 0, manifest_files: 0, metadata_files: 1}
+             } = report
+
+      assert Path.wildcard("#{tmp_dir}/**/*.metadata.json") == []
+      assert {:ok, _catalog, false} = Catalog.table_exists?(catalog, table_ident)
+    end
+
+    @tag :tmp_dir
+    test "keeps the table files by default", %{tmp_dir: tmp_dir} do
+      catalog = Catalog.load("local", %{type: "memory", warehouse: "file://#{tmp_dir}"})
+      table_ident = TableIdent.from_string("shop.orders")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+      {:ok, catalog, _table} = Catalog.create_table(catalog, table_ident, fields)
+
+      assert {:ok, _catalog, ^table_ident} = Catalog.drop_table(catalog, table_ident)
+      assert [_metadata_file] = Path.wildcard("#{tmp_dir}/**/*.metadata.json")
+    end
+  end
+
+  describe "drop_namespace/3" do
+    @tag :tmp_dir
+    test "fails for a namespace with tables", %{tmp_dir: tmp_dir} do
+      catalog =
+        Catalog.load("local", %{
+          type: "sql",
+          uri: "sqlite://#{tmp_dir}/catalog.db?mode=rwc",
+          warehouse: "file://#{tmp_dir}/warehouse"
+        })
+
+      namespace = NamespaceIdent.new("shop")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, namespace)
+      {:ok, catalog, _} =
+        Catalog.create_table(catalog, TableIdent.from_string("shop.orders"), fields)
+
+      assert {:error, catalog, %ExIceberg.Error{kind: :namespace_not_empty}} =
+               Catalog.drop_namespace(catalog, namespace)
+
+      assert {:ok, _catalog, [_ | _]} = Catalog.list_namespaces(catalog)
+    end
+
+    @tag :tmp_dir
+    test "drops nested namespaces and tables with cascade", %{tmp_dir: tmp_dir} do
+      catalog = Catalog.load("local", %{type: "memory", warehouse: "file://#{tmp_dir}"})
+      namespace = NamespaceIdent.new("shop")
+      orders = TableIdent.from_string("shop.orders")
+      events = TableIdent.from_string("shop.staging.events")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, namespace)
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop.staging"))
+      {:ok, catalog, _} = Catalog.create_table(catalog, orders, fields)
+      {:ok, catalog, _} = Catalog.create_table(catalog, events, fields)
+
+      assert {:ok, catalog, results} =
+               Catalog.drop_namespace(catalog, namespace, cascade: true, dry_run: true)
+
+      assert Enum.all?(results, &(&1.status == :would_drop))
+      assert Enum.count(results) == 4
+      assert {:ok, catalog, true} = Catalog.table_exists?(catalog, events)
+
+      assert {:ok, catalog, results} =
+               Catalog.drop_namespace(catalog, namespace, cascade: true, purge: true)
+
+      assert %{kind: :table, status: :purged, error: nil, deleted_files: %{metadata_files: 1}} =
+               Enum.find(results, &(&1.identifier == events))
+
+      assert [
+               %{identifier: %NamespaceIdent{parts: ["shop", "staging"]}, status: :dropped},
+               %{identifier: ^namespace, status: :dropped}
+             ] = Enum.filter(results, &(&1.kind == :namespace))
+
+      assert Path.wildcard("#{tmp_dir}/**/*.metadata.json") == []
+      assert {:ok, _catalog, []} = Catalog.list_namespaces(catalog)
+    end
+  end
+
+  describe "register_table/4" do
+    @tag :tmp_dir
+    test "registers a table from its metadata file", %{tmp_dir: tmp_dir} do
+      warehouse = "file://#{tmp_dir}/warehouse"
+      table_ident = TableIdent.from_string("shop.orders")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      source = Catalog.load("source", %{type: "memory", warehouse: warehouse})
+      {:ok, source, _} = Catalog.create_namespace(source, NamespaceIdent.new("shop"))
+      {:ok, _source, _table} = Catalog.create_table(source, table_ident, fields)
+      [metadata_path] = Path.wildcard("#{tmp_dir}/warehouse/**/*.metadata.json")
+
+      target = Catalog.load("target", %{type: "memory", warehouse: warehouse})
+      {:ok, target, _} = Catalog.create_namespace(target, NamespaceIdent.new("shop"))
+
+      assert {:ok, target, table} =
+               Catalog.register_table(target, table_ident, "file://#{metadata_path}")
+
+      assert Table.identifier(table) == table_ident
+      assert {:ok, _target, true} = Catalog.table_exists?(target, table_ident)
+    end
+
+    @tag :tmp_dir
+    test "is not supported by SQL catalogs", %{tmp_dir: tmp_dir} do
+      catalog =
+        Catalog.load("local", %{
+          type: "sql",
+          uri: "sqlite://#{tmp_dir}/catalog.db?mode=rwc",
+          warehouse: "file://#{tmp_dir}/warehouse"
+        })
+
+      assert {:error, _catalog, %ExIceberg.Error{kind: :not_supported}} =
+               Catalog.register_table(
+                 catalog,
+                 TableIdent.from_string("shop.orders"),
+                 "file://#{tmp_dir}/warehouse/orders.metadata.json"
+               )
+    end
+
+    test "fails for a missing metadata file" do
+      catalog = Catalog.load("local", %{type: "memory"})
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+
+      assert {:error, _catalog, %ExIceberg.Error{}} =
+               Catalog.register_table(
+                 catalog,
+                 TableIdent.from_string("shop.orders"),
+                 "memory://warehouse/missing.metadata.json"
+               )
+    end
+  end
+
+  test "rejects identity overrides for catalogs other than REST" do
+    catalog = Catalog.load("local", %{type: "memory"})
+
+    assert {:error, _catalog, %ExIceberg.Error{kind: :not_supported}} =
+             Catalog.list_namespaces(catalog, token: "tenant-token")
+
+    assert {:error, _catalog, %ExIceberg.Error{kind: :not_supported}} =
+             Catalog.list_namespaces(catalog, subject_token: "user-token")
+
+    assert_raise ArgumentError, fn ->
+      Catalog.list_namespaces(catalog, token: "tenant-token", subject_token: "user-token")
+    end
+  end
+
+  test "works with catalogs built by their own module" do
+    catalog = ExIceberg.Memory.Catalog.new("local")
+
+    assert {:ok, ^catalog, []} = Catalog.list_namespaces(catalog)
+  end
+end
@@ -0,0 +1,28 @@
+defmodule ExIceberg.Glue.CatalogTest do
+  use ExUnit.Case, async: true
+
+  alias ExIceberg.Glue.Catalog
+
+  describe "new/2" do
+    test "creates a catalog without contacting Glue" do
+      catalog =
+        Catalog.new("test", %{
+          warehouse: "s3://warehouse/glue",
+          endpoint: "http://localhost:5000",
+          region: "us-east-1",
+          access_key_id: "testing",
+          secret_access_key: "testing"
+        })
+
+      assert %Catalog{} = catalog
+      assert catalog.config.region == "us-east-1"
+      assert is_reference(catalog.nif_catalog_resource)
+    end
+
+    test "raises for an unsupported warehouse location" do
+      assert_raise ExIceberg.Error, ~r/Failed to create catalog/, fn ->
+        Catalog.new("test", %{warehouse: "gs://warehouse/glue", region: "us-east-1"})
+      end
+    end
+  end
+end
@@ -0,0 +1,30 @@
+defmodule ExIceberg.Hms.CatalogTest do
+  use ExUnit.Case, async: true
+
+  alias ExIceberg.Hms.Catalog
+
+  describe "new/2" do
+    test "creates a catalog without contacting the metastore" do
+      catalog =
+        Catalog.new("hive", %{
+          address: "127.0.0.1:9083",
+          warehouse: "s3://warehouse/hive",
+          thrift_transport: "framed"
+        })
+
+      assert %Catalog{} = catalog
+      assert catalog.config.address == "127.0.0.1:9083"
+      assert is_reference(catalog.nif_catalog_resource)
+    end
+
+    test "raises for an invalid thrift transport" do
+      assert_raise ExIceberg.Error, ~r/Invalid value for thrift_transport: http/, fn ->
+        Catalog.new("hive", %{
+          address: "127.0.0.1:9083",
+          warehouse: "s3://warehouse/hive",
+          thrift_transport: "http"
+        })
+      end
+    end
+  end
+end
@@ -0,0 +1,69 @@
+defmodule ExIceberg.Memory.CatalogTest do
+  use ExUnit.Case, async: true
+
+  alias ExIceberg.Memory.Catalog
+  alias ExIceberg.{NamespaceIdent, Table, TableIdent}
+
+  defmodule OrderSchema do
+    use ExIceberg.Schema
+
+    schema "orders" do
+      field :id, :long, required: true
+      field :customer, :string
+    end
+  end
+
+  describe "new/2" do
+    test "creates a catalog with an in-memory warehouse" do
+      catalog = Catalog.new("test")
+
+      assert %Catalog{} = catalog
+      assert catalog.config.warehouse == "memory://warehouse"
+      assert is_reference(catalog.nif_catalog_resource)
+    end
+
+    test "raises for an unsupported warehouse" do
+      assert_raise ExIceberg.Error, ~r/Invalid FileIO configuration/, fn ->
+        Catalog.new("test", %{warehouse: "hdfs://namenode/warehouse"})
+      end
+    end
+  end
+
+  describe "table lifecycle" do
+    setup do
+      catalog = Catalog.new("test")
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+
+      %{catalog: catalog}
+    end
+
+    test "creates, loads, renames and drops tables", %{catalog: catalog} do
+      table_ident = TableIdent.from_string("shop.orders")
+
+      assert {:ok, catalog, [%NamespaceIdent{parts: ["shop"]}]} =
+               Catalog.list_namespaces(catalog)
+
+      assert {:ok, catalog, %Table{}} = OrderSchema.create_table(catalog, table_ident)
+      assert {:ok, catalog, true} = Catalog.table_exists?(catalog, table_ident)
+
+      assert {:ok, catalog, table} = Catalog.load_table(catalog, table_ident)
+      assert Table.identifier(table) == table_ident
+      assert %{"location" => "memory://warehouse/shop/orders"} = Table.metadata(table)
+      assert Table.vended_credentials(table) == nil
+
+      renamed_ident = TableIdent.from_string("shop.archived_orders")
+
+      assert {:ok, catalog, %{"renamed" => "shop.orders -> shop.archived_orders"}} =
+               Catalog.rename_table(catalog, table_ident, renamed_ident)
+
+      assert {:ok, catalog, ^renamed_ident} = Catalog.drop_table(catalog, renamed_ident)
+      assert {:ok, _catalog, false} = Catalog.table_exists?(catalog, renamed_ident)
+    end
+
+    test "returns errors for missing tables", %{catalog: catalog} do
+      table_ident = TableIdent.from_string("shop.missing")
+
+      assert {:error, _catalog, %ExIceberg.Error{}} = Catalog.load_table(catalog, table_ident)
+    end
+  end
+end
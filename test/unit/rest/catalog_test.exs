@@ -2,7 +2,7 @@ defmodule ExIceberg.Rest.CatalogTest do
   use ExUnit.Case, async: true
 
   alias ExIceberg.Rest.Catalog
-  alias ExIceberg.TableIdent
+  alias ExIceberg.{NamespaceIdent, TableIdent}
 
   describe "new/2" do
     test "creates a catalog with basic config" do
@@ -33,6 +33,175 @@ defmodule ExIceberg.Rest.CatalogTest do
       assert catalog.config.scope == "catalog"
       assert is_reference(catalog.nif_catalog_resource)
     end
+
+    test "creates a catalog with HTTP options" do
+      config = %{
+        uri: "http://localhost:8181",
+        connect_timeout: 1_000,
+        read_timeout: 5_000,
+        proxy: "http://localhost:3128",
+        max_retries: 3
+      }
+
+      catalog = Catalog.new("http_test", config)
+
+      assert catalog.config.proxy == "http://localhost:3128"
+      assert is_reference(catalog.nif_catalog_resource)
+    end
+
+    test "creates a catalog with extra properties and headers" do
+      config = %{
+        uri: "http://localhost:8181",
+        properties: %{"prefix" => "my-prefix"},
+        headers: [{"X-Iceberg-Access-Delegation", "vended-credentials"}]
+      }
+
+      catalog = Catalog.new("props_test", config)
+
+      assert catalog.config.properties == %{"prefix" => "my-prefix"}
+      assert is_reference(catalog.nif_catalog_resource)
+    end
+
+    test "creates a catalog with FileIO properties" do
+      config = %{
+        uri: "http://localhost:8181",
+        warehouse: "file://#{System.tmp_dir!()}/ex_iceberg_warehouse",
+        file_io: %{"s3.region" => "local-01", "s3.path-style-access" => true}
+      }
+
+      catalog = Catalog.new("test", config)
+
+      assert catalog.config.file_io["s3.path-style-access"] == "true"
+    end
+
+    test "accepts a warehouse on storage without FileIO support" do
+      config = %{uri: "http://localhost:8181", warehouse: "hdfs://namenode/warehouse"}
+
+      assert %Catalog{} = Catalog.new("test", config)
+    end
+
+    test "raises on an invalid header name" do
+      config = %{uri: "http://localhost:8181", headers: [{"bad header", "value"}]}
+
+      assert_raise ExIceberg.Error, ~r/Invalid header/, fn -> Catalog.new("test", config) end
+    end
+
+    test "raises when the CA bundle cannot be read" do
+      config = %{uri: "https://localhost:8181", cacertfile: "/nonexistent/ca.pem"}
+
+      assert_raise ExIceberg.Error, ~r/cacertfile/, fn -> Catalog.new("test", config) end
+    end
+  end
+
+  describe "invalid identifiers" do
+    setup do
+      config = %{uri: "http://localhost:8181"}
+      catalog = Catalog.new("test", config)
+      {:ok, catalog: catalog}
+    end
+
+    test "create_namespace rejects an empty namespace", %{catalog: catalog} do
+      namespace = NamespaceIdent.from_list([])
+
+      assert {:error, ^catalog, %ExIceberg.Error{kind: :invalid_argument}} =
+               Catalog.create_namespace(catalog, namespace, %{})
+    end
+
+    test "load_table rejects an empty table name", %{catalog: catalog} do
+      table_ident = TableIdent.new(NamespaceIdent.new("namespace"), "")
+
+      assert {:error, ^catalog, %ExIceberg.Error{kind: :invalid_argument}} =
+               Catalog.load_table(catalog, table_ident)
+    end
+  end
+
+  describe "timeout option" do
+    setup do
+      # Non-routable address, so the connection attempt hangs
+      config = %{uri: "http://10.255.255.1:8181"}
+      catalog = Catalog.new("test", config)
+      {:ok, catalog: catalog}
+    end
+
+    test "aborts the request once the timeout expires", %{catalog: catalog} do
+      assert {:error, ^catalog, %ExIceberg.Error{kind: :timeout}} =
+               Catalog.list_namespaces(catalog, timeout: 100)
+
+      # The aborted request must not reply later
+      refute_receive {ref, _} when is_reference(ref), 200
+    end
+
+    test "aborts the request when the caller exits", %{catalog: catalog} do
+      task = Task.async(fn -> Catalog.list_namespaces(catalog) end)
+
+      assert Task.shutdown(task, 100) == nil
+    end
+  end
+
+  describe "token_info/1" do
+    test "returns nil without a token" do
+      catalog = Catalog.new("test", %{uri: "http://localhost:8181"})
+
+      assert {:ok, ^catalog, nil} = Catalog.token_info(catalog)
+    end
+
+    test "reports a static token as not refreshable" do
+      catalog = Catalog.new("test", %{uri: "http://localhost:8181", token: "opaque-token"})
+
+      assert {:ok, ^catalog, %{expires_at: nil, refreshable: false}} =
+               Catalog.token_info(catalog)
+    end
+  end
+
+  describe "refresh_token/2" do
+    test "is not supported without a credential" do
+      catalog = Catalog.new("test", %{uri: "http://localhost:8181", token: "opaque-token"})
+
+      assert {:error, ^catalog, %ExIceberg.Error{kind: :not_supported}} =
+               Catalog.refresh_token(catalog)
+    end
+
+    test "reports token endpoint failures" do
+      config = %{
+        uri: "http://localhost:8181",
+        credential: "client:secret",
+        oauth2_server_uri: "http://localhost:1/token"
+      }
+
+      catalog = Catalog.new("test", config)
+
+      assert {:error, ^catalog, %ExIceberg.Error{message: "Failed to request OAuth2 token"}} =
+               Catalog.refresh_token(catalog)
+    end
+  end
+
+  describe "config/2" do
+    test "reports server connection errors" do
+      catalog = Catalog.new("test", %{uri: "http://localhost:1", max_retries: 0})
+
+      assert {:error, ^catalog, %ExIceberg.Error{message: "Failed to load catalog config" <> _}} =
+               Catalog.config(catalog)
+    end
+  end
+
+  describe "identity options" do
+    test "requests a token for a per-call credential" do
+      config = %{uri: "http://localhost:8181", oauth2_server_uri: "http://localhost:1/token"}
+      catalog = Catalog.new("test", config)
+
+      assert {:error, ^catalog, %ExIceberg.Error{message: "Failed to request OAuth2 token"}} =
+               Catalog.list_namespaces(catalog, credential: "tenant:secret")
+
+      assert {:ok, ^catalog, nil} = Catalog.token_info(catalog)
+    end
+
+    test "rejects both a token and a credential" do
+      catalog = Catalog.new("test", %{uri: "http://localhost:8181"})
+
+      assert_raise ArgumentError, fn ->
+        Catalog.list_namespaces(catalog, token: "t", credential: "client:secret")
+      end
+    end
   end
 
   describe "rename_table/3" do
@@ -48,9 +217,24 @@ defmodule ExIceberg.Rest.CatalogTest do
 
       result = Catalog.rename_table(catalog, src_ident, dest_ident)
 
-      assert {:error, ^catalog, reason} = result
-      assert is_binary(reason)
-      assert String.contains?(reason, "Failed to rename table")
+      assert {:error, ^catalog, %ExIceberg.Error{} = error} = result
+      assert error.kind == :unexpected
+      assert error.http_status == nil
+      assert String.contains?(error.message, "Failed to rename table")
+    end
+
+    test "replies to each calling process when called concurrently", %{catalog: catalog} do
+      results =
+        1..5
+        |> Task.async_stream(fn i ->
+          src_ident = TableIdent.from_string("namespace.table_#{i}")
+          dest_ident = TableIdent.from_string("namespace.renamed_#{i}")
+          Catalog.rename_table(catalog, src_ident, dest_ident)
+        end)
+        |> Enum.to_list()
+
+      assert length(results) == 5
+      assert Enum.all?(results, &match?({:ok, {:error, ^catalog, %ExIceberg.Error{}}}, &1))
     end
 
     test "validates function signature", %{catalog: catalog} do
@@ -0,0 +1,28 @@
+defmodule ExIceberg.RuntimeTest do
+  use ExUnit.Case, async: false
+
+  alias ExIceberg.Runtime
+
+  describe "load_data/0" do
+    test "passes nil for unset options" do
+      Application.delete_env(:ex_iceberg, :runtime)
+
+      assert Runtime.load_data() == %{
+               worker_threads: nil,
+               max_blocking_threads: nil,
+               thread_name: nil
+             }
+    end
+
+    test "reads options from the application environment" do
+      Application.put_env(:ex_iceberg, :runtime, worker_threads: 2, thread_name: "iceberg")
+      on_exit(fn -> Application.delete_env(:ex_iceberg, :runtime) end)
+
+      assert Runtime.load_data() == %{
+               worker_threads: 2,
+               max_blocking_threads: nil,
+               thread_name: "iceberg"
+             }
+    end
+  end
+end
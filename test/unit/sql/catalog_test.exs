@@ -0,0 +1,78 @@
+defmodule ExIceberg.Sql.CatalogTest do
+  use ExUnit.Case, async: true
+
+  alias ExIceberg.Sql.Catalog
+  alias ExIceberg.{NamespaceIdent, Table, TableIdent}
+
+  @moduletag :tmp_dir
+
+  defp new_catalog(tmp_dir, name \\ "test") do
+    Catalog.new(name, %{
+      uri: "sqlite://#{tmp_dir}/catalog.db?mode=rwc",
+      warehouse: "file://#{tmp_dir}/warehouse"
+    })
+  end
+
+  describe "new/2" do
+    test "creates a catalog on a SQLite file", %{tmp_dir: tmp_dir} do
+      catalog = new_catalog(tmp_dir)
+
+      assert %Catalog{} = catalog
+      assert catalog.config.name == "test"
+      assert is_reference(catalog.nif_catalog_resource)
+      assert File.exists?(Path.join(tmp_dir, "catalog.db"))
+    end
+
+    test "raises for a non SQLite uri", %{tmp_dir: tmp_dir} do
+      assert_raise ExIceberg.Error, ~r/Unsupported SQL catalog uri/, fn ->
+        Catalog.new("test", %{
+          uri: "postgres://localhost/iceberg",
+          warehouse: "file://#{tmp_dir}/warehouse"
+        })
+      end
+    end
+
+    test "raises for invalid pool properties", %{tmp_dir: tmp_dir} do
+      assert_raise ExIceberg.Error, ~r/pool.max-connections/, fn ->
+        Catalog.new("test", %{
+          uri: "sqlite://#{tmp_dir}/catalog.db?mode=rwc",
+          warehouse: "file://#{tmp_dir}/warehouse",
+          properties: %{"pool.max-connections" => "many"}
+        })
+      end
+    end
+  end
+
+  describe "table lifecycle" do
+    test "keeps tables across catalog instances", %{tmp_dir: tmp_dir} do
+      catalog = new_catalog(tmp_dir)
+      table_ident = TableIdent.from_string("shop.orders")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+      assert {:ok, _catalog, %Table{}} = Catalog.create_table(catalog, table_ident, fields)
+
+      reopened = new_catalog(tmp_dir)
+      assert {:ok, reopened, true} = Catalog.table_exists?(reopened, table_ident)
+      assert {:ok, reopened, table} = Catalog.load_table(reopened, table_ident)
+      assert Table.identifier(table) == table_ident
+
+      renamed_ident = TableIdent.from_string("shop.archived_orders")
+
+      assert {:ok, reopened, %{"renamed" => "shop.orders -> shop.archived_orders"}} =
+               Catalog.rename_table(reopened, table_ident, renamed_ident)
+
+      assert {:ok, reopened, ^renamed_ident} = Catalog.drop_table(reopened, renamed_ident)
+      assert {:ok, _reopened, false} = Catalog.table_exists?(reopened, renamed_ident)
+    end
+
+    test "keeps catalogs sharing a database apart", %{tmp_dir: tmp_dir} do
+      {:ok, _catalog, _} =
+        tmp_dir
+        |> new_catalog("first")
+        |> Catalog.create_namespace(NamespaceIdent.new("shop"))
+
+      assert {:ok, _catalog, []} = tmp_dir |> new_catalog("second") |> Catalog.list_namespaces()
+    end
+  end
+end
@@ -1,6 +1,69 @@
 defmodule ExIceberg.TableTest do
   use ExUnit.Case, async: true
-  # Table tests were removed as Table can only be created via catalog operations
+
+  alias ExIceberg.{Catalog, NamespaceIdent, Table, TableIdent}
+
+  describe "from_metadata/2" do
+    @tag :tmp_dir
+    test "opens a read-only table from its metadata file", %{tmp_dir: tmp_dir} do
+      catalog = Catalog.load("local", %{type: "memory", warehouse: "file://#{tmp_dir}"})
+      table_ident = TableIdent.from_string("shop.orders")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+      {:ok, _catalog, created} = Catalog.create_table(catalog, table_ident, fields)
+      [metadata_path] = Path.wildcard("#{tmp_dir}/**/*.metadata.json")
+
+      assert {:ok, table} = Table.from_metadata("file://#{metadata_path}")
+      assert Table.metadata(table)["table_uuid"] == Table.metadata(created)["table_uuid"]
+      assert Table.identifier(table).namespace == NamespaceIdent.new("static-table")
+
+      assert :ok = Table.invalidate_cache(table)
+      assert %{"location" => _} = Table.metadata(table)
+      assert Table.vended_credentials(table) == nil
+      assert {:error, %ExIceberg.Error{kind: :not_supported}} = Table.refresh(table)
+    end
+
+    @tag :tmp_dir
+    test "uses the given identifier", %{tmp_dir: tmp_dir} do
+      catalog = Catalog.load("local", %{type: "memory", warehouse: "file://#{tmp_dir}"})
+      table_ident = TableIdent.from_string("shop.orders")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+      {:ok, _catalog, _table} = Catalog.create_table(catalog, table_ident, fields)
+      [metadata_path] = Path.wildcard("#{tmp_dir}/**/*.metadata.json")
+
+      assert {:ok, table} =
+               Table.from_metadata("file://#{metadata_path}", identifier: table_ident)
+
+      assert Table.identifier(table) == table_ident
+    end
+
+    @tag :tmp_dir
+    test "scans a static table", %{tmp_dir: tmp_dir} do
+      catalog = Catalog.load("local", %{type: "memory", warehouse: "file://#{tmp_dir}"})
+      table_ident = TableIdent.from_string("shop.orders")
+      fields = [ExIceberg.Types.field("id", :long, required: true)]
+
+      {:ok, catalog, _} = Catalog.create_namespace(catalog, NamespaceIdent.new("shop"))
+      {:ok, _catalog, _table} = Catalog.create_table(catalog, table_ident, fields)
+      [metadata_path] = Path.wildcard("#{tmp_dir}/**/*.metadata.json")
+
+      {:ok, table} = Table.from_metadata("file://#{metadata_path}")
+
+      # One Arrow IPC stream with the schema message and no record batches
+      assert {:ok, stream} = Table.scan(table, columns: ["id"])
+      assert [<<0xFFFFFFFF::32, _::binary>>] = Enum.to_list(stream)
+
+      assert {:error, %ExIceberg.Error{}} = Table.scan(table, columns: ["missing"])
+    end
+
+    test "fails for a missing metadata file" do
+      assert {:error, %ExIceberg.Error{}} =
+               Table.from_metadata("memory://warehouse/missing.metadata.json")
+    end
+  end
 end
 
 defmodule ExIceberg.Table.MetadataTableTest do